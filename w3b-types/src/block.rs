@@ -1,6 +1,7 @@
 use std::fmt;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use w3b_types_abi::{Address, Bytes, Bytes32, Bytes8, Uint256, Uint64};
 use w3b_types_core::hex::HexVisitor;
 
 use super::hex::Hex;
@@ -82,9 +83,100 @@ impl<'de> Deserialize<'de> for BlockNumber {
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    pub hash: Option<Bytes32>,
+    pub parent_hash: Bytes32,
+    pub sha3_uncles: Bytes32,
+    pub miner: Address,
+    pub state_root: Bytes32,
+    pub transactions_root: Bytes32,
+    pub receipts_root: Bytes32,
+    pub logs_bloom: Bytes,
+    pub difficulty: Uint256,
+    pub number: Option<Uint64>,
+    pub gas_limit: Uint64,
+    pub gas_used: Uint64,
+    pub timestamp: Uint64,
+    pub extra_data: Bytes,
+    pub mix_hash: Option<Bytes32>,
+    pub nonce: Option<Bytes8>,
+    pub base_fee_per_gas: Option<Uint256>,
+    pub withdrawals_root: Option<Bytes32>,
+    pub blob_gas_used: Option<Uint64>,
+    pub excess_blob_gas: Option<Uint64>,
+    pub parent_beacon_block_root: Option<Bytes32>,
+}
+
+/// A block with its transactions, either as hashes with `TX = Bytes32`, or as full objects.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block<TX = Bytes32> {
+    #[serde(flatten)]
+    pub header: Header,
+    pub total_difficulty: Option<Uint256>,
+    pub size: Option<Uint64>,
+    // Uncles are returned without their transactions.
+    #[serde(default = "Vec::new")]
+    pub transactions: Vec<TX>,
+    #[serde(default)]
+    pub uncles: Vec<Bytes32>,
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub index: Uint64,
+    pub validator_index: Uint64,
+    pub address: Address,
+    /// Amount in Gwei.
+    pub amount: Uint64,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BlockId {
+    Hash(Bytes32),
+    Number(BlockNumber),
+}
+
+impl Default for BlockId {
+    #[inline]
+    fn default() -> Self {
+        BlockId::Number(BlockNumber::default())
+    }
+}
+
+impl From<Bytes32> for BlockId {
+    #[inline]
+    fn from(value: Bytes32) -> Self {
+        BlockId::Hash(value)
+    }
+}
+
+impl<T: Into<BlockNumber>> From<T> for BlockId {
+    #[inline]
+    fn from(value: T) -> Self {
+        BlockId::Number(value.into())
+    }
+}
+
+impl Serialize for BlockId {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BlockId::Hash(hash) => hash.serialize(serializer),
+            BlockId::Number(block_number) => block_number.serialize(serializer),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::BlockNumber;
+    use w3b_types_abi::Bytes32;
+
+    use super::{Block, BlockNumber};
 
     #[test]
     fn serialize() {
@@ -111,4 +203,56 @@ mod tests {
             BlockNumber::Number(0x1_000_000_000_000),
         );
     }
+
+    #[test]
+    fn deserialize_block() {
+        let block: Block = serde_json::from_str(
+            r#"{
+                "hash": "0x0b6a3a5fc6df2e2ad86a0b2cc5f23d8d4ac2e1e3bc5e7c1b1ab7a3a4b1c3d2e1",
+                "parentHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                "miner": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+                "stateRoot": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                "transactionsRoot": "0x3333333333333333333333333333333333333333333333333333333333333333",
+                "receiptsRoot": "0x4444444444444444444444444444444444444444444444444444444444444444",
+                "logsBloom": "0x00",
+                "difficulty": "0x0",
+                "number": "0x12a05f2",
+                "gasLimit": "0x1c9c380",
+                "gasUsed": "0xe4e1c0",
+                "timestamp": "0x65f3a2b0",
+                "extraData": "0x",
+                "mixHash": "0x5555555555555555555555555555555555555555555555555555555555555555",
+                "nonce": "0x0000000000000000",
+                "baseFeePerGas": "0x3b9aca00",
+                "withdrawalsRoot": "0x6666666666666666666666666666666666666666666666666666666666666666",
+                "blobGasUsed": "0x40000",
+                "excessBlobGas": "0x0",
+                "parentBeaconBlockRoot": "0x7777777777777777777777777777777777777777777777777777777777777777",
+                "size": "0x1f4",
+                "transactions": [
+                    "0x8888888888888888888888888888888888888888888888888888888888888888"
+                ],
+                "uncles": [],
+                "withdrawals": [
+                    {
+                        "index": "0x1",
+                        "validatorIndex": "0x2",
+                        "address": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+                        "amount": "0x3"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(u64::from(block.header.number.unwrap()), 0x12a05f2);
+        assert_eq!(u64::from(block.header.blob_gas_used.unwrap()), 0x40000);
+        assert_eq!(
+            block.transactions,
+            vec![Bytes32::from_hex(format!("0x{}", "88".repeat(32))).unwrap()],
+        );
+        assert_eq!(u64::from(block.withdrawals.unwrap()[0].amount.clone()), 3);
+        assert!(block.total_difficulty.is_none());
+    }
 }
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use w3b_types_abi::{Address, Bytes, Bytes32, Uint256, Uint64};
+
+use super::transaction::AccessListItem;
+
+/// A message call executed without creating a transaction, for `eth_call` and `eth_estimateGas`.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<Uint64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// Temporary changes to account states for the duration of a call, keyed by account address.
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Uint64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Replaces the whole storage of the account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<Bytes32, Bytes32>>,
+    /// Replaces the given storage slots, keeping the others.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<Bytes32, Bytes32>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use w3b_types_abi::{Address, Bytes, Uint64};
+
+    use super::{AccountOverride, CallRequest, StateOverride};
+
+    #[test]
+    fn serialize_call_request() {
+        let call = CallRequest {
+            to: Some(Address::new([0x11; 20])),
+            gas: Some(Uint64::from(21000_u64)),
+            data: Some(Bytes::new(vec![0x12, 0x34])),
+            ..CallRequest::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&call).unwrap(),
+            format!(
+                r#"{{"to":"0x{}","gas":"0x5208","data":"0x1234"}}"#,
+                "11".repeat(20),
+            ),
+        );
+    }
+
+    #[test]
+    fn serialize_state_override() {
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            Address::new([0x22; 20]),
+            AccountOverride {
+                nonce: Some(Uint64::from(1_u64)),
+                code: Some(Bytes::new(vec![0x60])),
+                ..AccountOverride::default()
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_string(&overrides).unwrap(),
+            format!(
+                r#"{{"0x{}":{{"nonce":"0x1","code":"0x60"}}}}"#,
+                "22".repeat(20)
+            ),
+        );
+    }
+}
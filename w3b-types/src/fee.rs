@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use w3b_types_abi::{Uint256, Uint64};
+
+/// Base fees and priority fee percentiles over a range of blocks, from `eth_feeHistory`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+    pub oldest_block: Uint64,
+    /// Base fee of each block, followed by the base fee of the block after the newest one.
+    pub base_fee_per_gas: Vec<Uint256>,
+    pub gas_used_ratio: Vec<f64>,
+    /// Blob base fee of each block, followed by the one of the block after the newest one.
+    #[serde(default)]
+    pub base_fee_per_blob_gas: Vec<Uint256>,
+    #[serde(default)]
+    pub blob_gas_used_ratio: Vec<f64>,
+    /// Priority fees at the requested percentiles of each block, weighted by gas used.
+    #[serde(default)]
+    pub reward: Vec<Vec<Uint256>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeeHistory;
+
+    #[test]
+    fn deserialize() {
+        let history: FeeHistory = serde_json::from_str(
+            r#"{
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x3b9aca00", "0x3b9aca01", "0x3b9aca02"],
+                "gasUsedRatio": [0.5, 0.25],
+                "reward": [["0x1"], ["0x2"]]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(u64::from(history.oldest_block), 16);
+        assert_eq!(history.base_fee_per_gas.len(), 3);
+        assert_eq!(history.reward.len(), 2);
+        assert!(history.base_fee_per_blob_gas.is_empty());
+    }
+}
@@ -1,9 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use w3b_types_abi::{Address, Bytes32};
 
 use super::block::BlockNumber;
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Filter {
     #[serde(flatten)]
@@ -12,7 +12,7 @@ pub struct Filter {
     pub topics: Vec<Topic>,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
 pub enum FilterBlocks {
     #[serde(rename_all = "camelCase")]
@@ -25,9 +25,26 @@ pub enum FilterBlocks {
     },
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
 pub enum Topic {
     Hash(Bytes32),
     OneOf(Vec<Bytes32>),
 }
+
+/// Identifies a filter installed on a node.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct FilterId(String);
+
+impl FilterId {
+    #[inline]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
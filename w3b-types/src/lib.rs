@@ -6,11 +6,23 @@ pub use w3b_types_core;
 pub use w3b_types_abi::*;
 
 mod block;
+mod call;
+mod fee;
 mod filter;
 mod hex;
 mod log;
+mod proof;
+mod trace;
+mod transaction;
+mod txpool;
 
 pub use block::*;
+pub use call::*;
+pub use fee::*;
 pub use filter::*;
 pub use hex::*;
 pub use log::*;
+pub use proof::*;
+pub use trace::*;
+pub use transaction::*;
+pub use txpool::*;
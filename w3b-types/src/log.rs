@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use w3b_types_abi::{Address, Bytes, Bytes32, Uint64};
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Log {
     pub address: Address,
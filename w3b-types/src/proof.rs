@@ -0,0 +1,53 @@
+use serde::Deserialize;
+use w3b_types_abi::{Address, Bytes, Bytes32, Uint256, Uint64};
+
+/// An account and some of its storage slots, with Merkle-Patricia proofs, from `eth_getProof`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: Address,
+    /// Nodes from the state root down to the account.
+    pub account_proof: Vec<Bytes>,
+    pub balance: Uint256,
+    pub code_hash: Bytes32,
+    pub nonce: Uint64,
+    pub storage_hash: Bytes32,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+    /// The slot, which nodes may echo back without its leading zeros.
+    pub key: Uint256,
+    pub value: Uint256,
+    /// Nodes from the storage root down to the slot.
+    pub proof: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccountProof;
+
+    #[test]
+    fn deserialize() {
+        let proof: AccountProof = serde_json::from_str(&format!(
+            r#"{{
+                "address": "0x{address}",
+                "accountProof": ["0xf8518080"],
+                "balance": "0x0",
+                "codeHash": "0x{hash}",
+                "nonce": "0x0",
+                "storageHash": "0x{hash}",
+                "storageProof": [{{ "key": "0x0", "value": "0x2a", "proof": [] }}]
+            }}"#,
+            address = "11".repeat(20),
+            hash = "22".repeat(32),
+        ))
+        .unwrap();
+
+        assert_eq!(proof.account_proof.len(), 1);
+        assert_eq!(proof.storage_proof[0].key.as_bytes(), &[0; 32][..]);
+        assert_eq!(proof.storage_proof[0].value.as_bytes()[31], 0x2a);
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use w3b_types_abi::{Address, Bytes, Bytes32, Uint256, Uint64};
+
+use super::block::BlockNumber;
+
+/// A call made during a transaction, as reported by geth's `callTracer`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// `CALL`, `STATICCALL`, `DELEGATECALL`, `CREATE`, `SELFDESTRUCT` and so on.
+    pub r#type: String,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: Option<Uint256>,
+    pub gas: Uint64,
+    pub gas_used: Uint64,
+    pub input: Bytes,
+    pub output: Option<Bytes>,
+    pub error: Option<String>,
+    pub revert_reason: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+    /// Only reported with the `withLog` option.
+    #[serde(default)]
+    pub logs: Vec<CallLog>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CallLog {
+    pub address: Address,
+    pub topics: Vec<Bytes32>,
+    pub data: Bytes,
+}
+
+/// The state of an account as reported by geth's `prestateTracer`, with only the fields that
+/// changed in the post state of its diff mode.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AccountState {
+    pub balance: Option<Uint256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytes>,
+    #[serde(default)]
+    pub storage: HashMap<Bytes32, Bytes32>,
+}
+
+/// The accounts a transaction touched, before and after it, from the diff mode of geth's
+/// `prestateTracer`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PrestateDiff {
+    pub pre: HashMap<Address, AccountState>,
+    pub post: HashMap<Address, AccountState>,
+}
+
+/// The result of tracing one transaction of a block, failing on its own.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTrace<T> {
+    /// Missing before geth 1.11.
+    pub tx_hash: Option<Bytes32>,
+    pub result: Option<T>,
+    pub error: Option<String>,
+}
+
+/// A Parity-style trace of an action taken during a transaction, or of a block reward.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    #[serde(flatten)]
+    pub action: Action,
+    pub result: Option<ActionResult>,
+    pub error: Option<String>,
+    pub subtraces: u64,
+    /// Indices of the trace within its parents, from the outermost call.
+    pub trace_address: Vec<u64>,
+    pub transaction_hash: Option<Bytes32>,
+    pub transaction_position: Option<u64>,
+    pub block_hash: Option<Bytes32>,
+    pub block_number: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", content = "action", rename_all = "lowercase")]
+pub enum Action {
+    #[serde(rename_all = "camelCase")]
+    Call {
+        from: Address,
+        to: Address,
+        value: Uint256,
+        gas: Uint64,
+        input: Bytes,
+        /// `call`, `staticcall`, `delegatecall` or `callcode`.
+        call_type: String,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    Create {
+        from: Address,
+        value: Uint256,
+        gas: Uint64,
+        init: Bytes,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    Suicide {
+        address: Address,
+        refund_address: Address,
+        balance: Uint256,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    Reward {
+        author: Address,
+        value: Uint256,
+        /// `block` or `uncle`.
+        reward_type: String,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ActionResult {
+    #[serde(rename_all = "camelCase")]
+    Call { gas_used: Uint64, output: Bytes },
+
+    #[serde(rename_all = "camelCase")]
+    Create {
+        gas_used: Uint64,
+        code: Bytes,
+        address: Address,
+    },
+}
+
+/// Selects the traces `trace_filter` returns, all of them by default.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Vec<Address>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Vec<Address>>,
+    /// Number of matching traces to skip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+/// What `trace_replayTransaction` reports.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+    Trace,
+    StateDiff,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+    pub output: Bytes,
+    pub trace: Option<Vec<TransactionTrace>>,
+    pub state_diff: Option<StateDiff>,
+}
+
+/// The changes a transaction made to the accounts it touched.
+pub type StateDiff = HashMap<Address, AccountDiff>;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AccountDiff {
+    pub balance: Diff<Uint256>,
+    pub nonce: Diff<Uint64>,
+    pub code: Diff<Bytes>,
+    pub storage: HashMap<Bytes32, Diff<Bytes32>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum Diff<T> {
+    #[serde(rename = "=")]
+    Same,
+    /// The account was created with the value.
+    #[serde(rename = "+")]
+    Born(T),
+    /// The account was destroyed with the value.
+    #[serde(rename = "-")]
+    Died(T),
+    #[serde(rename = "*")]
+    Changed { from: T, to: T },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, ActionResult, Diff, TraceResults};
+
+    #[test]
+    fn deserialize_replay() {
+        let results: TraceResults = serde_json::from_str(&format!(
+            r#"{{
+                "output": "0x",
+                "trace": [{{
+                    "action": {{
+                        "from": "0x{from}",
+                        "to": "0x{to}",
+                        "value": "0x1",
+                        "gas": "0x5208",
+                        "input": "0x",
+                        "callType": "call"
+                    }},
+                    "result": {{ "gasUsed": "0x0", "output": "0x" }},
+                    "subtraces": 0,
+                    "traceAddress": [],
+                    "type": "call"
+                }}],
+                "stateDiff": {{
+                    "0x{from}": {{
+                        "balance": {{ "*": {{ "from": "0x2", "to": "0x1" }} }},
+                        "nonce": {{ "*": {{ "from": "0x0", "to": "0x1" }} }},
+                        "code": "=",
+                        "storage": {{}}
+                    }},
+                    "0x{to}": {{
+                        "balance": {{ "+": "0x1" }},
+                        "nonce": {{ "+": "0x0" }},
+                        "code": {{ "+": "0x" }},
+                        "storage": {{}}
+                    }}
+                }}
+            }}"#,
+            from = "11".repeat(20),
+            to = "22".repeat(20),
+        ))
+        .unwrap();
+
+        let trace = &results.trace.unwrap()[0];
+        assert!(matches!(trace.action, Action::Call { .. }));
+        assert!(matches!(trace.result, Some(ActionResult::Call { .. })));
+
+        let diffs: Vec<_> = results
+            .state_diff
+            .unwrap()
+            .values()
+            .map(|diff| diff.code.clone())
+            .collect();
+        assert!(diffs.contains(&Diff::Same));
+        assert!(diffs.iter().any(|diff| matches!(diff, Diff::Born(_))));
+    }
+}
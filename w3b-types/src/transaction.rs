@@ -0,0 +1,362 @@
+use serde::{de, Deserialize, Deserializer, Serialize};
+use w3b_types_abi::{Address, Bytes, Bytes32, Uint256, Uint64};
+
+use super::log::Log;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub hash: Bytes32,
+    pub nonce: Uint64,
+    pub block_hash: Option<Bytes32>,
+    pub block_number: Option<Uint64>,
+    pub transaction_index: Option<Uint64>,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: Uint256,
+    pub gas: Uint64,
+    pub input: Bytes,
+    pub v: Uint64,
+    pub r: Uint256,
+    pub s: Uint256,
+    #[serde(flatten)]
+    pub envelope: Envelope,
+}
+
+/// The fields specific to each transaction type.
+///
+/// Transactions without a type are legacy ones from before EIP-2718, and types not listed here,
+/// such as the deposit transactions of rollups, keep their raw fields as [`Envelope::Unknown`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum Envelope {
+    #[serde(rename = "0x0", rename_all = "camelCase")]
+    Legacy {
+        gas_price: Uint256,
+        /// Missing on transactions signed before EIP-155.
+        chain_id: Option<Uint64>,
+    },
+
+    #[serde(rename = "0x1", rename_all = "camelCase")]
+    Eip2930 {
+        chain_id: Uint64,
+        gas_price: Uint256,
+        access_list: Vec<AccessListItem>,
+    },
+
+    #[serde(rename = "0x2", rename_all = "camelCase")]
+    Eip1559 {
+        chain_id: Uint64,
+        max_fee_per_gas: Uint256,
+        max_priority_fee_per_gas: Uint256,
+        access_list: Vec<AccessListItem>,
+    },
+
+    #[serde(rename = "0x3", rename_all = "camelCase")]
+    Eip4844 {
+        chain_id: Uint64,
+        max_fee_per_gas: Uint256,
+        max_priority_fee_per_gas: Uint256,
+        max_fee_per_blob_gas: Uint256,
+        access_list: Vec<AccessListItem>,
+        blob_versioned_hashes: Vec<Bytes32>,
+    },
+
+    #[serde(rename = "0x4", rename_all = "camelCase")]
+    Eip7702 {
+        chain_id: Uint64,
+        max_fee_per_gas: Uint256,
+        max_priority_fee_per_gas: Uint256,
+        access_list: Vec<AccessListItem>,
+        authorization_list: Vec<Authorization>,
+    },
+
+    /// The fields of a transaction type not known here, including `type`.
+    #[serde(skip)]
+    Unknown(serde_json::Map<String, serde_json::Value>),
+}
+
+impl<'de> Deserialize<'de> for Envelope {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = serde_json::Map::deserialize(deserializer)?;
+
+        match fields.get("type").and_then(serde_json::Value::as_str) {
+            Some("0x0") | Some("0x1") | Some("0x2") | Some("0x3") | Some("0x4") => {}
+            Some(_) => return Ok(Envelope::Unknown(fields)),
+            None => {
+                fields.insert("type".to_owned(), "0x0".into());
+            }
+        }
+
+        Envelope::deserialize(serde_json::Value::Object(fields)).map_err(de::Error::custom)
+    }
+}
+
+/// An authorization for an account to delegate to the code of another, from EIP-7702.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    pub chain_id: Uint256,
+    pub address: Address,
+    pub nonce: Uint64,
+    pub y_parity: Uint64,
+    pub r: Uint256,
+    pub s: Uint256,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<Bytes32>,
+}
+
+/// A transaction for the node to sign with one of its own accounts, for `eth_sendTransaction`.
+///
+/// Fields left out are filled in by the node.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRequest {
+    pub from: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Uint64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<Uint64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Uint256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<Uint64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
+}
+
+impl TransactionRequest {
+    #[inline]
+    pub fn new(from: Address) -> Self {
+        Self {
+            from,
+            to: None,
+            nonce: None,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            value: None,
+            data: None,
+            chain_id: None,
+            access_list: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+    pub transaction_hash: Bytes32,
+    pub transaction_index: Uint64,
+    pub block_hash: Bytes32,
+    pub block_number: Uint64,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub r#type: Option<Uint64>,
+    /// Either 1 for success or 0 for failure, missing on receipts from before Byzantium.
+    pub status: Option<Uint64>,
+    /// The post-transaction state root, only on receipts from before Byzantium.
+    pub root: Option<Bytes32>,
+    pub cumulative_gas_used: Uint64,
+    pub gas_used: Uint64,
+    pub effective_gas_price: Option<Uint256>,
+    pub blob_gas_used: Option<Uint64>,
+    pub blob_gas_price: Option<Uint256>,
+    pub contract_address: Option<Address>,
+    pub logs: Vec<Log>,
+    pub logs_bloom: Bytes,
+}
+
+impl TransactionReceipt {
+    /// Returns whether the transaction succeeded, if the receipt has a status.
+    #[inline]
+    pub fn succeeded(&self) -> Option<bool> {
+        self.status.clone().map(|status| u64::from(status) == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use w3b_types_abi::{Address, Uint256};
+
+    use super::{Envelope, Transaction, TransactionReceipt, TransactionRequest};
+
+    fn transaction(envelope: &str) -> Transaction {
+        serde_json::from_str(&format!(
+            r#"{{
+                "hash": "0x{hash}",
+                "nonce": "0x1",
+                "blockHash": "0x{hash}",
+                "blockNumber": "0x10",
+                "transactionIndex": "0x0",
+                "from": "0x{address}",
+                "to": "0x{address}",
+                "value": "0xde0b6b3a7640000",
+                "gas": "0x5208",
+                "input": "0x",
+                "v": "0x1",
+                "r": "0x2",
+                "s": "0x3",
+                {}
+            }}"#,
+            envelope,
+            hash = "11".repeat(32),
+            address = "22".repeat(20),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn deserialize_transaction_types() {
+        let legacy = transaction(r#""type": "0x0", "gasPrice": "0x1""#);
+        assert!(matches!(
+            legacy.envelope,
+            Envelope::Legacy { chain_id: None, .. }
+        ));
+
+        let eip2930 = transaction(
+            r#""type": "0x1", "chainId": "0x1", "gasPrice": "0x1", "accessList": [
+                { "address": "0x2222222222222222222222222222222222222222", "storageKeys": [] }
+            ]"#,
+        );
+        assert!(
+            matches!(eip2930.envelope, Envelope::Eip2930 { access_list, .. } if access_list.len() == 1)
+        );
+
+        let eip1559 = transaction(
+            r#""type": "0x2", "chainId": "0x1", "maxFeePerGas": "0x2",
+                "maxPriorityFeePerGas": "0x1", "accessList": []"#,
+        );
+        assert!(matches!(eip1559.envelope, Envelope::Eip1559 { .. }));
+
+        let eip4844 = transaction(
+            r#""type": "0x3", "chainId": "0x1", "maxFeePerGas": "0x2",
+                "maxPriorityFeePerGas": "0x1", "maxFeePerBlobGas": "0x3", "accessList": [],
+                "blobVersionedHashes": [
+                    "0x0100000000000000000000000000000000000000000000000000000000000000"
+                ]"#,
+        );
+        assert!(matches!(
+            eip4844.envelope,
+            Envelope::Eip4844 { blob_versioned_hashes, .. } if blob_versioned_hashes.len() == 1
+        ));
+
+        let eip7702 = transaction(
+            r#""type": "0x4", "chainId": "0x1", "maxFeePerGas": "0x2",
+                "maxPriorityFeePerGas": "0x1", "accessList": [], "authorizationList": [{
+                    "chainId": "0x1",
+                    "address": "0x2222222222222222222222222222222222222222",
+                    "nonce": "0x0",
+                    "yParity": "0x1",
+                    "r": "0x2",
+                    "s": "0x3"
+                }]"#,
+        );
+        assert!(matches!(
+            eip7702.envelope,
+            Envelope::Eip7702 { authorization_list, .. } if authorization_list.len() == 1
+        ));
+    }
+
+    #[test]
+    fn deserialize_untyped_transaction() {
+        let untyped = transaction(r#""gasPrice": "0x1", "chainId": "0x1""#);
+        assert!(matches!(
+            untyped.envelope,
+            Envelope::Legacy {
+                chain_id: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn deserialize_unknown_transaction_type() {
+        let deposit = transaction(
+            r#""type": "0x7e", "sourceHash": "0x01", "mint": "0x0", "isSystemTx": false"#,
+        );
+
+        match deposit.envelope {
+            Envelope::Unknown(fields) => {
+                assert_eq!(fields["type"], "0x7e");
+                assert_eq!(fields["isSystemTx"], false);
+                assert!(!fields.contains_key("hash"));
+            }
+            envelope => panic!("unexpected envelope {:?}", envelope),
+        }
+    }
+
+    #[test]
+    fn deserialize_receipt() {
+        let receipt: TransactionReceipt = serde_json::from_str(&format!(
+            r#"{{
+                "transactionHash": "0x{hash}",
+                "transactionIndex": "0x0",
+                "blockHash": "0x{hash}",
+                "blockNumber": "0x10",
+                "from": "0x{address}",
+                "to": null,
+                "type": "0x2",
+                "status": "0x1",
+                "cumulativeGasUsed": "0xa410",
+                "gasUsed": "0x5208",
+                "effectiveGasPrice": "0x3b9aca00",
+                "contractAddress": "0x{address}",
+                "logs": [{{
+                    "address": "0x{address}",
+                    "topics": ["0x{hash}"],
+                    "data": "0x",
+                    "blockNumber": "0x10",
+                    "blockHash": "0x{hash}",
+                    "transactionHash": "0x{hash}",
+                    "transactionIndex": "0x0",
+                    "logIndex": "0x0",
+                    "removed": false
+                }}],
+                "logsBloom": "0x00"
+            }}"#,
+            hash = "11".repeat(32),
+            address = "22".repeat(20),
+        ))
+        .unwrap();
+
+        assert_eq!(receipt.succeeded(), Some(true));
+        assert_eq!(u64::from(receipt.gas_used.clone()), 21000);
+        assert!(receipt.contract_address.is_some());
+        assert_eq!(receipt.logs.len(), 1);
+    }
+
+    #[test]
+    fn serialize_transaction_request() {
+        let request = TransactionRequest {
+            to: Some(Address::new([0x22; 20])),
+            value: Some(Uint256::from(1_000_000_000_u64)),
+            ..TransactionRequest::new(Address::new([0x11; 20]))
+        };
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            format!(
+                r#"{{"from":"0x{}","to":"0x{}","value":"0x3b9aca00"}}"#,
+                "11".repeat(20),
+                "22".repeat(20),
+            ),
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+use w3b_types_abi::{Address, Uint64};
+
+use super::transaction::Transaction;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TxPoolStatus {
+    pub pending: Uint64,
+    pub queued: Uint64,
+}
+
+/// The pooled transactions by sender and nonce, either executable or queued behind a nonce gap.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TxPoolContent {
+    pub pending: HashMap<Address, BTreeMap<u64, Transaction>>,
+    pub queued: HashMap<Address, BTreeMap<u64, Transaction>>,
+}
+
+/// The pooled transactions of one sender by nonce.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TxPoolContentFrom {
+    pub pending: BTreeMap<u64, Transaction>,
+    pub queued: BTreeMap<u64, Transaction>,
+}
+
+/// Summaries of the pooled transactions by sender and nonce, such as
+/// `0x...: 0 wei + 21000 gas × 1000000000 wei`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TxPoolInspect {
+    pub pending: HashMap<Address, BTreeMap<u64, String>>,
+    pub queued: HashMap<Address, BTreeMap<u64, String>>,
+}
@@ -0,0 +1,239 @@
+use std::{collections::HashMap, future::Future};
+
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use w3b_types::*;
+
+use super::execute;
+use crate::{error::Error, namespace::Namespace, provider::Provider};
+
+/// One of geth's built-in tracers, with its options and the result it returns per transaction.
+pub trait Tracer {
+    type Output: DeserializeOwned + Send + 'static;
+
+    /// Returns the trace config, with the tracer name and its options.
+    fn config(&self) -> serde_json::Value;
+}
+
+/// Reports the calls made by a transaction as a tree of [`CallFrame`]s.
+#[derive(Clone, Debug, Default)]
+pub struct CallTracer {
+    /// Skips the calls made by the top call.
+    pub only_top_call: bool,
+    pub with_log: bool,
+}
+
+impl Tracer for CallTracer {
+    type Output = CallFrame;
+
+    fn config(&self) -> serde_json::Value {
+        json!({
+            "tracer": "callTracer",
+            "tracerConfig": { "onlyTopCall": self.only_top_call, "withLog": self.with_log },
+        })
+    }
+}
+
+/// Reports the state of the accounts a transaction touched, before it.
+#[derive(Clone, Debug, Default)]
+pub struct PrestateTracer;
+
+impl Tracer for PrestateTracer {
+    type Output = HashMap<Address, AccountState>;
+
+    fn config(&self) -> serde_json::Value {
+        json!({ "tracer": "prestateTracer" })
+    }
+}
+
+/// Reports the state of the accounts a transaction touched, before and after it.
+#[derive(Clone, Debug, Default)]
+pub struct PrestateDiffTracer;
+
+impl Tracer for PrestateDiffTracer {
+    type Output = PrestateDiff;
+
+    fn config(&self) -> serde_json::Value {
+        json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } })
+    }
+}
+
+/// Counts the calls made by a transaction by function selector and calldata size, keyed as
+/// `0x27dc297e-128`.
+#[derive(Clone, Debug, Default)]
+pub struct FourByteTracer;
+
+impl Tracer for FourByteTracer {
+    type Output = HashMap<String, u64>;
+
+    fn config(&self) -> serde_json::Value {
+        json!({ "tracer": "4byteTracer" })
+    }
+}
+
+#[derive(Clone)]
+pub struct Debug<T: Provider> {
+    provider: T,
+}
+
+impl<T: Provider> Debug<T> {
+    /// Replays a mined transaction with the tracer.
+    pub fn trace_transaction<R: Tracer>(
+        &self,
+        hash: impl Into<Bytes32>,
+        tracer: &R,
+    ) -> impl Future<Output = Result<R::Output, Error>> {
+        let hash = serde_json::to_value(hash.into()).unwrap();
+        execute(
+            &self.provider,
+            "debug_traceTransaction",
+            vec![hash, tracer.config()],
+        )
+    }
+
+    /// Executes a call with the tracer as [`Eth::call`](crate::eth::Eth::call) does, against the
+    /// latest block by default.
+    pub fn trace_call<R: Tracer>(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+        tracer: &R,
+    ) -> impl Future<Output = Result<R::Output, Error>> {
+        let call = serde_json::to_value(call).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        execute(
+            &self.provider,
+            "debug_traceCall",
+            vec![call, block_id, tracer.config()],
+        )
+    }
+
+    /// Replays all the transactions of a block with the tracer, in order.
+    pub fn trace_block_by_number<R: Tracer>(
+        &self,
+        block_number: impl Into<BlockNumber>,
+        tracer: &R,
+    ) -> impl Future<Output = Result<Vec<BlockTrace<R::Output>>, Error>> {
+        let block_number = serde_json::to_value(block_number.into()).unwrap();
+        execute(
+            &self.provider,
+            "debug_traceBlockByNumber",
+            vec![block_number, tracer.config()],
+        )
+    }
+}
+
+impl<T: Provider> Namespace<T> for Debug<T> {
+    #[inline]
+    fn new(provider: T) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    fn provider(&self) -> &T {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::{CallTracer, Debug, FourByteTracer, PrestateDiffTracer, PrestateTracer};
+    use crate::{namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn trace_calls() {
+        let mock = MockProvider::new();
+        let debug = Debug::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+        let from = format!("0x{}", "22".repeat(20));
+        let to = format!("0x{}", "33".repeat(20));
+
+        mock.expect("debug_traceTransaction")
+            .params(vec![
+                json!(hash),
+                json!({
+                    "tracer": "callTracer",
+                    "tracerConfig": { "onlyTopCall": false, "withLog": false },
+                }),
+            ])
+            .returns(json!({
+                "type": "CALL",
+                "from": from,
+                "to": to,
+                "value": "0x0",
+                "gas": "0x7530",
+                "gasUsed": "0x5208",
+                "input": "0xa9059cbb",
+                "error": "execution reverted",
+                "calls": [{
+                    "type": "STATICCALL",
+                    "from": to,
+                    "to": from,
+                    "gas": "0x1000",
+                    "gasUsed": "0x100",
+                    "input": "0x",
+                    "output": "0x",
+                }],
+            }));
+        mock.expect("debug_traceBlockByNumber")
+            .params(vec![json!("0x2a"), json!({ "tracer": "4byteTracer" })])
+            .returns(json!([{ "txHash": hash, "result": { "0xa9059cbb-64": 1 } }]));
+
+        let frame =
+            block_on(debug.trace_transaction(hash.clone(), &CallTracer::default())).unwrap();
+        assert_eq!(frame.error.as_deref(), Some("execution reverted"));
+        assert_eq!(frame.calls[0].r#type, "STATICCALL");
+        assert!(frame.calls[0].value.is_none());
+
+        let traces = block_on(debug.trace_block_by_number(42_u64, &FourByteTracer)).unwrap();
+        assert_eq!(traces[0].tx_hash, Some(hash));
+        assert_eq!(traces[0].result.as_ref().unwrap()["0xa9059cbb-64"], 1);
+    }
+
+    #[test]
+    fn trace_prestate() {
+        let mock = MockProvider::new();
+        let debug = Debug::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+        let address = format!("0x{}", "22".repeat(20));
+        let call = CallRequest {
+            to: Some(Address::from_hex(&address).unwrap()),
+            ..CallRequest::default()
+        };
+
+        // Geth reports nonces as JSON numbers here, unlike everywhere else.
+        mock.expect("debug_traceCall")
+            .params(vec![
+                json!(call),
+                json!("0x5"),
+                json!({ "tracer": "prestateTracer" }),
+            ])
+            .returns(json!({ &address: { "balance": "0x10", "nonce": 3 } }));
+        mock.expect("debug_traceTransaction")
+            .params(vec![
+                json!(hash),
+                json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }),
+            ])
+            .returns(json!({
+                "pre": { &address: { "balance": "0x10", "nonce": 3, "code": "0x00" } },
+                "post": { &address: { "nonce": 4 } },
+            }));
+
+        let address = Address::from_hex(address).unwrap();
+
+        let prestate =
+            block_on(debug.trace_call(&call, Some(5_u64.into()), &PrestateTracer)).unwrap();
+        assert_eq!(prestate[&address].nonce, Some(3));
+        assert!(prestate[&address].code.is_none());
+
+        let diff = block_on(debug.trace_transaction(hash, &PrestateDiffTracer)).unwrap();
+        assert_eq!(diff.pre[&address].nonce, Some(3));
+        assert_eq!(diff.post[&address].nonce, Some(4));
+        assert!(diff.post[&address].balance.is_none());
+        assert_eq!(mock.remaining(), 0);
+    }
+}
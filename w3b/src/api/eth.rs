@@ -1,7 +1,18 @@
+use std::{future::Future, time::Duration};
+
+use futures::TryFutureExt;
 use serde::de::DeserializeOwned;
+use serde_json::json;
 use w3b_types::*;
 
-use crate::{error::Error, json_rpc::Response, namespace::Namespace, provider::Provider};
+use crate::{
+    error::Error,
+    filter_watcher::{FilterKind, FilterWatcher},
+    json_rpc::{Request, Response},
+    namespace::Namespace,
+    provider::{Provider, PubSubProvider},
+    subscription::Subscription,
+};
 
 #[derive(Clone)]
 pub struct Eth<T: Provider> {
@@ -9,48 +20,368 @@ pub struct Eth<T: Provider> {
 }
 
 impl<T: Provider> Eth<T> {
-    pub async fn block_number(&self) -> Result<u64, Error> {
-        self.execute("eth_blockNumber", vec![])
-            .await
-            .map(Hex::inner)
+    pub fn block_number(&self) -> impl Future<Output = Result<u64, Error>> {
+        self.execute("eth_blockNumber", vec![]).map_ok(Hex::inner)
     }
 
-    pub async fn balance(
+    pub fn balance(
         &self,
         address: impl Into<Address>,
         block_number: impl Into<Option<BlockNumber>>,
-    ) -> Result<Uint256, Error> {
+    ) -> impl Future<Output = Result<Uint256, Error>> {
         let address = serde_json::to_value(address.into()).unwrap();
         let block_number = serde_json::to_value(block_number.into().unwrap_or_default()).unwrap();
         self.execute("eth_getBalance", vec![address, block_number])
-            .await
     }
 
-    pub async fn logs(&self, filter: impl Into<Filter>) -> Result<Vec<Log>, Error> {
+    pub fn logs(&self, filter: impl Into<Filter>) -> impl Future<Output = Result<Vec<Log>, Error>> {
         let filter = serde_json::to_value(filter.into()).unwrap();
-        self.execute("eth_getLogs", vec![filter]).await
+        self.execute("eth_getLogs", vec![filter])
     }
 
-    pub async fn transaction_count(
+    pub fn transaction_count(
         &self,
         address: impl Into<Address>,
         block_number: impl Into<Option<BlockNumber>>,
-    ) -> Result<Uint64, Error> {
+    ) -> impl Future<Output = Result<Uint64, Error>> {
         let address = serde_json::to_value(address.into()).unwrap();
         let block_number = serde_json::to_value(block_number.into().unwrap_or_default()).unwrap();
         self.execute("eth_getTransactionCount", vec![address, block_number])
-            .await
     }
 
-    async fn execute<U: DeserializeOwned>(
+    /// Returns the block with the hashes of its transactions.
+    pub fn block(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> impl Future<Output = Result<Option<Block>, Error>> {
+        self.fetch_block(block_id.into(), false)
+    }
+
+    /// Returns the block with its transactions as full objects.
+    pub fn block_with_transactions(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> impl Future<Output = Result<Option<Block<Transaction>>, Error>> {
+        self.fetch_block(block_id.into(), true)
+    }
+
+    fn fetch_block<TX: DeserializeOwned>(
+        &self,
+        block_id: BlockId,
+        full: bool,
+    ) -> impl Future<Output = Result<Option<Block<TX>>, Error>> {
+        let method = match block_id {
+            BlockId::Hash(_) => "eth_getBlockByHash",
+            BlockId::Number(_) => "eth_getBlockByNumber",
+        };
+
+        let block_id = serde_json::to_value(block_id).unwrap();
+        self.execute(method, vec![block_id, full.into()])
+    }
+
+    /// Returns the uncle, or ommer, at the given index of the block, without its transactions.
+    pub fn uncle(
+        &self,
+        block_id: impl Into<BlockId>,
+        index: u64,
+    ) -> impl Future<Output = Result<Option<Block>, Error>> {
+        let block_id = block_id.into();
+        let method = match block_id {
+            BlockId::Hash(_) => "eth_getUncleByBlockHashAndIndex",
+            BlockId::Number(_) => "eth_getUncleByBlockNumberAndIndex",
+        };
+
+        let block_id = serde_json::to_value(block_id).unwrap();
+        let index = serde_json::to_value(Hex::new(index)).unwrap();
+        self.execute(method, vec![block_id, index])
+    }
+
+    pub fn uncle_count(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> impl Future<Output = Result<u64, Error>> {
+        let block_id = block_id.into();
+        let method = match block_id {
+            BlockId::Hash(_) => "eth_getUncleCountByBlockHash",
+            BlockId::Number(_) => "eth_getUncleCountByBlockNumber",
+        };
+
+        let block_id = serde_json::to_value(block_id).unwrap();
+        self.execute(method, vec![block_id]).map_ok(Hex::inner)
+    }
+
+    pub fn transaction(
+        &self,
+        hash: impl Into<Bytes32>,
+    ) -> impl Future<Output = Result<Option<Transaction>, Error>> {
+        let hash = serde_json::to_value(hash.into()).unwrap();
+        self.execute("eth_getTransactionByHash", vec![hash])
+    }
+
+    pub fn transaction_by_block_and_index(
+        &self,
+        block_id: impl Into<BlockId>,
+        index: u64,
+    ) -> impl Future<Output = Result<Option<Transaction>, Error>> {
+        let block_id = block_id.into();
+        let method = match block_id {
+            BlockId::Hash(_) => "eth_getTransactionByBlockHashAndIndex",
+            BlockId::Number(_) => "eth_getTransactionByBlockNumberAndIndex",
+        };
+
+        let block_id = serde_json::to_value(block_id).unwrap();
+        let index = serde_json::to_value(Hex::new(index)).unwrap();
+        self.execute(method, vec![block_id, index])
+    }
+
+    /// Returns the receipt of a transaction, or `None` while it is still pending.
+    pub fn transaction_receipt(
+        &self,
+        hash: impl Into<Bytes32>,
+    ) -> impl Future<Output = Result<Option<TransactionReceipt>, Error>> {
+        let hash = serde_json::to_value(hash.into()).unwrap();
+        self.execute("eth_getTransactionReceipt", vec![hash])
+    }
+
+    pub fn block_receipts(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> impl Future<Output = Result<Option<Vec<TransactionReceipt>>, Error>> {
+        let block_id = serde_json::to_value(block_id.into()).unwrap();
+        self.execute("eth_getBlockReceipts", vec![block_id])
+    }
+
+    /// Executes a call without creating a transaction, against the latest block by default,
+    /// failing with [`Error::Revert`] if it reverts.
+    pub fn call(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> impl Future<Output = Result<Bytes, Error>> {
+        let call = serde_json::to_value(call).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        self.execute("eth_call", vec![call, block_id])
+            .map_err(Error::into_revert)
+    }
+
+    /// Executes a call as [`call`](Self::call) does, on top of temporary account state changes.
+    pub fn call_with_state_override(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+        state_override: &StateOverride,
+    ) -> impl Future<Output = Result<Bytes, Error>> {
+        let call = serde_json::to_value(call).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        let state_override = serde_json::to_value(state_override).unwrap();
+        self.execute("eth_call", vec![call, block_id, state_override])
+            .map_err(Error::into_revert)
+    }
+
+    /// Estimates the gas a call would use, against the node's default block unless given one.
+    pub fn estimate_gas(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> impl Future<Output = Result<u64, Error>> {
+        let mut params = vec![serde_json::to_value(call).unwrap()];
+
+        // Some nodes reject the block parameter, so it is only sent when given.
+        if let Some(block_id) = block_id.into() {
+            params.push(serde_json::to_value(block_id).unwrap());
+        }
+
+        self.execute("eth_estimateGas", params)
+            .map_ok(Hex::inner)
+            .map_err(Error::into_revert)
+    }
+
+    /// Sends a transaction signed by the node with one of its accounts, returning its hash.
+    pub fn send_transaction(
+        &self,
+        transaction: &TransactionRequest,
+    ) -> impl Future<Output = Result<Bytes32, Error>> {
+        let transaction = serde_json::to_value(transaction).unwrap();
+        self.execute("eth_sendTransaction", vec![transaction])
+    }
+
+    /// Sends a signed and encoded transaction, returning its hash.
+    pub fn send_raw_transaction(
+        &self,
+        transaction: impl Into<Bytes>,
+    ) -> impl Future<Output = Result<Bytes32, Error>> {
+        let transaction = serde_json::to_value(transaction.into()).unwrap();
+        self.execute("eth_sendRawTransaction", vec![transaction])
+    }
+
+    /// Returns the gas price suggested by the node for legacy transactions.
+    pub fn gas_price(&self) -> impl Future<Output = Result<Uint256, Error>> {
+        self.execute("eth_gasPrice", vec![])
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> impl Future<Output = Result<Uint256, Error>> {
+        self.execute("eth_maxPriorityFeePerGas", vec![])
+    }
+
+    /// Returns the blob base fee of the next block.
+    pub fn blob_base_fee(&self) -> impl Future<Output = Result<Uint256, Error>> {
+        self.execute("eth_blobBaseFee", vec![])
+    }
+
+    /// Returns the fee history of up to `block_count` blocks ending at `newest_block`, with the
+    /// priority fees paid at each of the given percentiles, between 0 and 100, in ascending order.
+    pub fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: impl Into<BlockNumber>,
+        reward_percentiles: &[f64],
+    ) -> impl Future<Output = Result<FeeHistory, Error>> {
+        let block_count = serde_json::to_value(Hex::new(block_count)).unwrap();
+        let newest_block = serde_json::to_value(newest_block.into()).unwrap();
+        let reward_percentiles = serde_json::to_value(reward_percentiles).unwrap();
+        self.execute(
+            "eth_feeHistory",
+            vec![block_count, newest_block, reward_percentiles],
+        )
+    }
+
+    pub fn code(
+        &self,
+        address: impl Into<Address>,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> impl Future<Output = Result<Bytes, Error>> {
+        let address = serde_json::to_value(address.into()).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        self.execute("eth_getCode", vec![address, block_id])
+    }
+
+    pub fn storage_at(
+        &self,
+        address: impl Into<Address>,
+        slot: impl Into<Uint256>,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> impl Future<Output = Result<Bytes32, Error>> {
+        let address = serde_json::to_value(address.into()).unwrap();
+        let slot = serde_json::to_value(slot.into()).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        self.execute("eth_getStorageAt", vec![address, slot, block_id])
+    }
+
+    /// Returns an account and the given storage slots with their Merkle-Patricia proofs, which
+    /// [`verify_proof`](crate::verify_proof) checks against the state root of the block.
+    pub fn proof(
+        &self,
+        address: impl Into<Address>,
+        storage_keys: &[Uint256],
+        block_id: impl Into<Option<BlockId>>,
+    ) -> impl Future<Output = Result<AccountProof, Error>> {
+        let address = serde_json::to_value(address.into()).unwrap();
+        let storage_keys = serde_json::to_value(storage_keys).unwrap();
+        let block_id = serde_json::to_value(block_id.into().unwrap_or_default()).unwrap();
+        self.execute("eth_getProof", vec![address, storage_keys, block_id])
+    }
+
+    /// Installs a log filter on the node, whose matching logs are then polled with
+    /// [`filter_changes`](Self::filter_changes).
+    pub fn new_filter(
+        &self,
+        filter: impl Into<Filter>,
+    ) -> impl Future<Output = Result<FilterId, Error>> {
+        let filter = serde_json::to_value(filter.into()).unwrap();
+        self.execute("eth_newFilter", vec![filter])
+    }
+
+    /// Installs a filter on the node for the hashes of new blocks.
+    pub fn new_block_filter(&self) -> impl Future<Output = Result<FilterId, Error>> {
+        self.execute("eth_newBlockFilter", vec![])
+    }
+
+    /// Installs a filter on the node for the hashes of new pending transactions.
+    pub fn new_pending_transaction_filter(&self) -> impl Future<Output = Result<FilterId, Error>> {
+        self.execute("eth_newPendingTransactionFilter", vec![])
+    }
+
+    /// Returns what the filter matched since it was last polled, as [`Log`]s for log filters or
+    /// as [`Bytes32`] hashes for block and pending transaction filters.
+    pub fn filter_changes<U: DeserializeOwned>(
+        &self,
+        id: &FilterId,
+    ) -> impl Future<Output = Result<Vec<U>, Error>> {
+        let id = serde_json::to_value(id).unwrap();
+        self.execute("eth_getFilterChanges", vec![id])
+    }
+
+    /// Returns all the logs matching a log filter.
+    pub fn filter_logs(&self, id: &FilterId) -> impl Future<Output = Result<Vec<Log>, Error>> {
+        let id = serde_json::to_value(id).unwrap();
+        self.execute("eth_getFilterLogs", vec![id])
+    }
+
+    /// Removes a filter from the node, returning whether it was installed.
+    pub fn uninstall_filter(&self, id: &FilterId) -> impl Future<Output = Result<bool, Error>> {
+        let id = serde_json::to_value(id).unwrap();
+        self.execute("eth_uninstallFilter", vec![id])
+    }
+
+    /// Watches the logs matching the filter by polling a node-side filter every `interval`.
+    pub fn watch_logs(
+        &self,
+        filter: impl Into<Filter>,
+        interval: Duration,
+    ) -> FilterWatcher<T, Log> {
+        FilterWatcher::new(self.clone(), FilterKind::Logs(filter.into()), interval)
+    }
+
+    /// Watches the hashes of new blocks by polling a node-side filter every `interval`.
+    pub fn watch_blocks(&self, interval: Duration) -> FilterWatcher<T, Bytes32> {
+        FilterWatcher::new(self.clone(), FilterKind::Blocks, interval)
+    }
+
+    /// Watches the hashes of new pending transactions by polling a node-side filter every
+    /// `interval`.
+    pub fn watch_pending_transactions(&self, interval: Duration) -> FilterWatcher<T, Bytes32> {
+        FilterWatcher::new(self.clone(), FilterKind::PendingTransactions, interval)
+    }
+
+    fn execute<U: DeserializeOwned>(
         &self,
         method: &str,
         params: Vec<serde_json::Value>,
-    ) -> Result<U, Error> {
-        let value = self.provider.execute(method, params).await?;
+    ) -> impl Future<Output = Result<U, Error>> {
+        super::execute(&self.provider, method, params)
+    }
+}
+
+impl<T: PubSubProvider> Eth<T> {
+    pub async fn subscribe_new_heads(&self) -> Result<Subscription<T, Header>, Error> {
+        self.subscribe(vec!["newHeads".into()]).await
+    }
+
+    pub async fn subscribe_logs(
+        &self,
+        filter: impl Into<Filter>,
+    ) -> Result<Subscription<T, Log>, Error> {
+        // Log subscriptions only follow new blocks, so the block range is not part of the filter.
+        let filter = filter.into();
+        let filter = json!({ "address": filter.address, "topics": filter.topics });
+        self.subscribe(vec!["logs".into(), filter]).await
+    }
+
+    pub async fn subscribe_new_pending_transactions(
+        &self,
+    ) -> Result<Subscription<T, Bytes32>, Error> {
+        self.subscribe(vec!["newPendingTransactions".into()]).await
+    }
+
+    async fn subscribe<U: DeserializeOwned>(
+        &self,
+        params: Vec<serde_json::Value>,
+    ) -> Result<Subscription<T, U>, Error> {
+        let request = Request::new("eth_subscribe".to_owned(), params);
+        let (value, notifications) = self.provider.subscribe(request).await?;
         let response: Response = serde_json::from_value(value)?;
-        let result = serde_json::from_value(response.result)?;
-        Ok(result)
+        let id = response.into_result()?;
+        Ok(Subscription::new(self.provider.clone(), id, notifications))
     }
 }
 
@@ -65,3 +396,237 @@ impl<T: Provider> Namespace<T> for Eth<T> {
         &self.provider
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::Eth;
+    use crate::{error::Error, namespace::Namespace, providers::MockProvider};
+
+    fn header() -> serde_json::Value {
+        let hash = format!("0x{}", "11".repeat(32));
+
+        json!({
+            "hash": hash,
+            "parentHash": hash,
+            "sha3Uncles": hash,
+            "miner": format!("0x{}", "22".repeat(20)),
+            "stateRoot": hash,
+            "transactionsRoot": hash,
+            "receiptsRoot": hash,
+            "logsBloom": "0x00",
+            "difficulty": "0x1",
+            "number": "0x2a",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x5",
+            "extraData": "0x",
+        })
+    }
+
+    #[test]
+    fn fetch_blocks_by_hash_or_number() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+
+        let mut block = header();
+        block["transactions"] = json!([hash]);
+
+        mock.expect("eth_getBlockByHash")
+            .params(vec![json!(hash), json!(false)])
+            .returns(&block);
+        mock.expect("eth_getBlockByNumber")
+            .params(vec![json!("0x2a"), json!(true)])
+            .returns(json!(null));
+
+        let block = block_on(eth.block(hash.clone())).unwrap().unwrap();
+        assert_eq!(block.transactions, vec![hash]);
+        assert!(block_on(eth.block_with_transactions(42_u64))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn fetch_uncles() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_getUncleCountByBlockNumber")
+            .params(vec![json!("latest")])
+            .returns("0x1");
+        mock.expect("eth_getUncleByBlockNumberAndIndex")
+            .params(vec![json!("latest"), json!("0x0")])
+            .returns(header());
+
+        assert_eq!(block_on(eth.uncle_count(BlockNumber::Latest)).unwrap(), 1);
+
+        let uncle = block_on(eth.uncle(BlockNumber::Latest, 0))
+            .unwrap()
+            .unwrap();
+        assert!(uncle.transactions.is_empty());
+        assert_eq!(u64::from(uncle.header.number.unwrap()), 42);
+    }
+
+    #[test]
+    fn fetch_transactions_and_receipts() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+
+        mock.expect("eth_getTransactionByBlockNumberAndIndex")
+            .params(vec![json!("0x2a"), json!("0x1")])
+            .returns(json!(null));
+        mock.expect("eth_getTransactionReceipt")
+            .params(vec![json!(hash)])
+            .returns(json!(null));
+        mock.expect("eth_getBlockReceipts")
+            .params(vec![json!(hash)])
+            .returns(json!([]));
+
+        assert!(block_on(eth.transaction_by_block_and_index(42_u64, 1))
+            .unwrap()
+            .is_none());
+        assert!(block_on(eth.transaction_receipt(hash.clone()))
+            .unwrap()
+            .is_none());
+        assert!(block_on(eth.block_receipts(hash))
+            .unwrap()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn call_and_decode_reverts() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let call = CallRequest {
+            to: Some(Address::new([0x11; 20])),
+            data: Some(Bytes::new(vec![0x06, 0xfd, 0xde, 0x03])),
+            ..CallRequest::default()
+        };
+
+        let mut state_override = StateOverride::new();
+        state_override.insert(
+            Address::new([0x11; 20]),
+            AccountOverride {
+                code: Some(Bytes::new(vec![0x00])),
+                ..AccountOverride::default()
+            },
+        );
+
+        mock.expect("eth_call")
+            .params(vec![json!(call), json!("latest")])
+            .returns("0x2a");
+        mock.expect("eth_call")
+            .params(vec![json!(call), json!("0x5"), json!(state_override)])
+            .rpc_error(3, "execution reverted: nope", Some(json!("0x08c379a0")));
+        mock.expect("eth_estimateGas")
+            .params(vec![json!(call)])
+            .rpc_error(-32000, "execution reverted", None);
+        mock.expect("eth_estimateGas")
+            .params(vec![json!(call)])
+            .rpc_error(
+                -32000,
+                "insufficient funds for transfer",
+                Some(json!("0x12")),
+            );
+
+        assert_eq!(
+            block_on(eth.call(&call, None)).unwrap(),
+            Bytes::new(vec![0x2a]),
+        );
+
+        match block_on(eth.call_with_state_override(&call, Some(5_u64.into()), &state_override)) {
+            Err(Error::Revert(data)) => assert_eq!(data, Bytes::new(vec![0x08, 0xc3, 0x79, 0xa0])),
+            result => panic!("unexpected {:?}", result),
+        }
+
+        match block_on(eth.estimate_gas(&call, None)) {
+            Err(Error::Revert(data)) => assert!(data.as_bytes().is_empty()),
+            result => panic!("unexpected {:?}", result),
+        }
+
+        // Other errors keep their hex data as is.
+        match block_on(eth.estimate_gas(&call, None)) {
+            Err(Error::Rpc { code: -32000, .. }) => {}
+            result => panic!("unexpected {:?}", result),
+        }
+    }
+
+    #[test]
+    fn send_transactions() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "33".repeat(32))).unwrap();
+        let transaction = TransactionRequest::new(Address::new([0x11; 20]));
+
+        mock.expect("eth_sendTransaction")
+            .params(vec![json!({ "from": format!("0x{}", "11".repeat(20)) })])
+            .returns(&hash);
+        mock.expect("eth_sendRawTransaction")
+            .params(vec![json!("0x02f8")])
+            .returns(&hash);
+
+        assert_eq!(block_on(eth.send_transaction(&transaction)).unwrap(), hash);
+        assert_eq!(
+            block_on(eth.send_raw_transaction(Bytes::new(vec![0x02, 0xf8]))).unwrap(),
+            hash,
+        );
+    }
+
+    #[test]
+    fn read_contract_state() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let address = Address::new([0x11; 20]);
+        let word = format!("0x{}", "00".repeat(31) + "2a");
+
+        mock.expect("eth_getCode")
+            .params(vec![json!(address), json!("latest")])
+            .returns("0x6080");
+        mock.expect("eth_getStorageAt")
+            .params(vec![json!(address), json!("0x1"), json!("0x10")])
+            .returns(&word);
+
+        assert_eq!(
+            block_on(eth.code(address.clone(), None)).unwrap(),
+            Bytes::new(vec![0x60, 0x80]),
+        );
+        assert_eq!(
+            block_on(eth.storage_at(address, 1_u64, Some(16_u64.into()))).unwrap(),
+            Bytes32::from_hex(word).unwrap(),
+        );
+    }
+
+    #[test]
+    fn fetch_proofs() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let address = Address::new([0x11; 20]);
+        let node = format!("0x{}", "ab".repeat(8));
+
+        mock.expect("eth_getProof")
+            .params(vec![json!(address), json!(["0x1"]), json!("0x10")])
+            .returns(json!({
+                "address": address,
+                "accountProof": [node],
+                "balance": "0x3e8",
+                "codeHash": format!("0x{}", "22".repeat(32)),
+                "nonce": "0x1",
+                "storageHash": format!("0x{}", "33".repeat(32)),
+                "storageProof": [{ "key": "0x01", "value": "0x2a", "proof": [node] }],
+            }));
+
+        let keys = [Uint256::from(1_u64)];
+        let proof = block_on(eth.proof(address.clone(), &keys, Some(16_u64.into()))).unwrap();
+        assert_eq!(proof.address, address);
+        assert_eq!(proof.account_proof, vec![Bytes::from_hex(&node).unwrap()]);
+        assert_eq!(proof.nonce, Uint64::from(1_u64));
+        assert_eq!(proof.storage_proof[0].key, keys[0]);
+        assert_eq!(proof.storage_proof[0].value, Uint256::from(0x2a_u64));
+    }
+}
@@ -1 +1,27 @@
+pub mod debug;
 pub mod eth;
+pub mod net;
+pub mod trace;
+pub mod txpool;
+pub mod web3_api;
+
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+
+use crate::{error::Error, json_rpc::Response, provider::Provider};
+
+/// Sends the request right away, so that calls queued on a batch provider keep their order
+/// and do not depend on when the returned future is first polled.
+fn execute<T: Provider, U: DeserializeOwned>(
+    provider: &T,
+    method: &str,
+    params: Vec<serde_json::Value>,
+) -> impl Future<Output = Result<U, Error>> {
+    let response = provider.execute(method, params);
+
+    async move {
+        let response: Response = serde_json::from_value(response.await?)?;
+        response.into_result()
+    }
+}
@@ -0,0 +1,67 @@
+use std::future::Future;
+
+use futures::{future, TryFutureExt};
+use w3b_types::Hex;
+
+use super::execute;
+use crate::{error::Error, namespace::Namespace, provider::Provider};
+
+#[derive(Clone)]
+pub struct Net<T: Provider> {
+    provider: T,
+}
+
+impl<T: Provider> Net<T> {
+    /// Returns the network id, which nodes report as a decimal string.
+    pub fn version(&self) -> impl Future<Output = Result<u64, Error>> {
+        execute(&self.provider, "net_version", vec![]).and_then(|version: String| {
+            future::ready(
+                version
+                    .parse()
+                    .map_err(|error| Error::Provider(Box::new(error))),
+            )
+        })
+    }
+
+    pub fn peer_count(&self) -> impl Future<Output = Result<u64, Error>> {
+        execute(&self.provider, "net_peerCount", vec![]).map_ok(Hex::inner)
+    }
+
+    pub fn listening(&self) -> impl Future<Output = Result<bool, Error>> {
+        execute(&self.provider, "net_listening", vec![])
+    }
+}
+
+impl<T: Provider> Namespace<T> for Net<T> {
+    #[inline]
+    fn new(provider: T) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    fn provider(&self) -> &T {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::Net;
+    use crate::{namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn network_status() {
+        let mock = MockProvider::new();
+        let net = Net::new(mock.clone());
+
+        mock.expect("net_version").returns("11155111");
+        mock.expect("net_peerCount").returns("0x19");
+        mock.expect("net_listening").returns(true);
+
+        assert_eq!(block_on(net.version()).unwrap(), 11_155_111);
+        assert_eq!(block_on(net.peer_count()).unwrap(), 25);
+        assert!(block_on(net.listening()).unwrap());
+    }
+}
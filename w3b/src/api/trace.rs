@@ -0,0 +1,182 @@
+use std::future::Future;
+
+use w3b_types::*;
+
+use super::execute;
+use crate::{error::Error, namespace::Namespace, provider::Provider};
+
+/// The Parity-style `trace_` namespace, served by erigon, nethermind and reth.
+#[derive(Clone)]
+pub struct Trace<T: Provider> {
+    provider: T,
+}
+
+impl<T: Provider> Trace<T> {
+    pub fn transaction(
+        &self,
+        hash: impl Into<Bytes32>,
+    ) -> impl Future<Output = Result<Vec<TransactionTrace>, Error>> {
+        let hash = serde_json::to_value(hash.into()).unwrap();
+        execute(&self.provider, "trace_transaction", vec![hash])
+    }
+
+    /// Returns the traces of all the transactions of a block and of its rewards, or `None` if the
+    /// block is unknown.
+    pub fn block(
+        &self,
+        block_number: impl Into<BlockNumber>,
+    ) -> impl Future<Output = Result<Option<Vec<TransactionTrace>>, Error>> {
+        let block_number = serde_json::to_value(block_number.into()).unwrap();
+        execute(&self.provider, "trace_block", vec![block_number])
+    }
+
+    pub fn filter(
+        &self,
+        filter: &TraceFilter,
+    ) -> impl Future<Output = Result<Vec<TransactionTrace>, Error>> {
+        let filter = serde_json::to_value(filter).unwrap();
+        execute(&self.provider, "trace_filter", vec![filter])
+    }
+
+    /// Replays a mined transaction, reporting the given trace types.
+    pub fn replay_transaction(
+        &self,
+        hash: impl Into<Bytes32>,
+        trace_types: &[TraceType],
+    ) -> impl Future<Output = Result<TraceResults, Error>> {
+        let hash = serde_json::to_value(hash.into()).unwrap();
+        let trace_types = serde_json::to_value(trace_types).unwrap();
+        execute(
+            &self.provider,
+            "trace_replayTransaction",
+            vec![hash, trace_types],
+        )
+    }
+}
+
+impl<T: Provider> Namespace<T> for Trace<T> {
+    #[inline]
+    fn new(provider: T) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    fn provider(&self) -> &T {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::Trace;
+    use crate::{namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn filter_traces() {
+        let mock = MockProvider::new();
+        let trace = Trace::new(mock.clone());
+        let author = Address::new([0x11; 20]);
+
+        mock.expect("trace_filter")
+            .params(vec![json!({
+                "fromBlock": "0x2a",
+                "toBlock": "0x2a",
+                "toAddress": [author],
+                "count": 10,
+            })])
+            .returns(json!([{
+                "action": { "author": author, "value": "0x1bc16d674ec80000", "rewardType": "block" },
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [],
+                "blockHash": format!("0x{}", "22".repeat(32)),
+                "blockNumber": 42,
+                "type": "reward",
+            }]));
+
+        let filter = TraceFilter {
+            from_block: Some(42_u64.into()),
+            to_block: Some(42_u64.into()),
+            to_address: Some(vec![author.clone()]),
+            count: Some(10),
+            ..TraceFilter::default()
+        };
+
+        let traces = block_on(trace.filter(&filter)).unwrap();
+        assert_eq!(traces[0].block_number, Some(42));
+
+        match &traces[0].action {
+            Action::Reward {
+                author: reward_author,
+                reward_type,
+                ..
+            } => {
+                assert_eq!(*reward_author, author);
+                assert_eq!(reward_type, "block");
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+    }
+
+    #[test]
+    fn replay_transactions_and_blocks() {
+        let mock = MockProvider::new();
+        let trace = Trace::new(mock.clone());
+        let hash = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+        let from = format!("0x{}", "22".repeat(20));
+        let created = format!("0x{}", "33".repeat(20));
+
+        mock.expect("trace_replayTransaction")
+            .params(vec![json!(hash), json!(["trace", "stateDiff"])])
+            .returns(json!({
+                "output": "0x",
+                "trace": [{
+                    "action": { "from": from, "value": "0x0", "gas": "0x10000", "init": "0x6000" },
+                    "result": { "gasUsed": "0x100", "code": "0x00", "address": created },
+                    "subtraces": 0,
+                    "traceAddress": [],
+                    "type": "create",
+                }],
+                "stateDiff": {},
+            }));
+        mock.expect("trace_block")
+            .params(vec![json!("0x2a")])
+            .returns(json!([{
+                "action": { "address": created, "refundAddress": from, "balance": "0x1" },
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [0],
+                "transactionHash": hash,
+                "transactionPosition": 0,
+                "blockNumber": 42,
+                "type": "suicide",
+            }]));
+        mock.expect("trace_block")
+            .params(vec![json!("0x2b")])
+            .returns(json!(null));
+
+        let results = block_on(
+            trace.replay_transaction(hash.clone(), &[TraceType::Trace, TraceType::StateDiff]),
+        )
+        .unwrap();
+        let traces = results.trace.unwrap();
+        assert!(matches!(traces[0].action, Action::Create { .. }));
+
+        match &traces[0].result {
+            Some(ActionResult::Create { address, .. }) => {
+                assert_eq!(*address, Address::from_hex(&created).unwrap());
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let traces = block_on(trace.block(42_u64)).unwrap().unwrap();
+        assert!(matches!(traces[0].action, Action::Suicide { .. }));
+        assert_eq!(traces[0].trace_address, vec![0]);
+        assert_eq!(traces[0].transaction_hash, Some(hash));
+        assert!(block_on(trace.block(43_u64)).unwrap().is_none());
+    }
+}
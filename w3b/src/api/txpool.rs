@@ -0,0 +1,135 @@
+use std::future::Future;
+
+use w3b_types::*;
+
+use super::execute;
+use crate::{error::Error, namespace::Namespace, provider::Provider};
+
+#[derive(Clone)]
+pub struct TxPool<T: Provider> {
+    provider: T,
+}
+
+impl<T: Provider> TxPool<T> {
+    pub fn status(&self) -> impl Future<Output = Result<TxPoolStatus, Error>> {
+        execute(&self.provider, "txpool_status", vec![])
+    }
+
+    pub fn content(&self) -> impl Future<Output = Result<TxPoolContent, Error>> {
+        execute(&self.provider, "txpool_content", vec![])
+    }
+
+    /// Returns a one-line summary of each transaction in the pool, cheaper than its content.
+    pub fn inspect(&self) -> impl Future<Output = Result<TxPoolInspect, Error>> {
+        execute(&self.provider, "txpool_inspect", vec![])
+    }
+
+    /// Returns the pooled transactions sent from the address.
+    pub fn content_from(
+        &self,
+        address: impl Into<Address>,
+    ) -> impl Future<Output = Result<TxPoolContentFrom, Error>> {
+        let address = serde_json::to_value(address.into()).unwrap();
+        execute(&self.provider, "txpool_contentFrom", vec![address])
+    }
+}
+
+impl<T: Provider> Namespace<T> for TxPool<T> {
+    #[inline]
+    fn new(provider: T) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    fn provider(&self) -> &T {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::TxPool;
+    use crate::{namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn inspect_pool() {
+        let mock = MockProvider::new();
+        let txpool = TxPool::new(mock.clone());
+        let sender = format!("0x{}", "11".repeat(20));
+        let summary = format!("0x{}: 1 wei + 21000 gas × 2 wei", "22".repeat(20));
+
+        mock.expect("txpool_status")
+            .returns(json!({ "pending": "0x2", "queued": "0x0" }));
+        mock.expect("txpool_inspect").returns(json!({
+            "pending": { &sender: { "7": summary, "8": summary } },
+            "queued": {},
+        }));
+
+        let status = block_on(txpool.status()).unwrap();
+        assert_eq!(u64::from(status.pending), 2);
+
+        let inspect = block_on(txpool.inspect()).unwrap();
+        let sender = Address::from_hex(sender).unwrap();
+        let nonces: Vec<_> = inspect.pending[&sender].keys().copied().collect();
+        assert_eq!(nonces, vec![7, 8]);
+        assert_eq!(inspect.pending[&sender][&7], summary);
+        assert!(inspect.queued.is_empty());
+    }
+
+    /// A pending transaction, with the block fields pools leave null.
+    fn pooled(nonce: u64) -> serde_json::Value {
+        json!({
+            "hash": format!("0x{:064x}", nonce),
+            "nonce": format!("0x{:x}", nonce),
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "from": format!("0x{}", "11".repeat(20)),
+            "to": format!("0x{}", "22".repeat(20)),
+            "value": "0x1",
+            "gas": "0x5208",
+            "gasPrice": "0x2",
+            "input": "0x",
+            "v": "0x1b",
+            "r": "0x1",
+            "s": "0x1",
+        })
+    }
+
+    #[test]
+    fn fetch_pool_content() {
+        let mock = MockProvider::new();
+        let txpool = TxPool::new(mock.clone());
+        let address = format!("0x{}", "11".repeat(20));
+        let sender = Address::from_hex(&address).unwrap();
+
+        mock.expect("txpool_content").returns(json!({
+            "pending": { &address: { "7": pooled(7) } },
+            "queued": { &address: { "9": pooled(9) } },
+        }));
+        mock.expect("txpool_contentFrom")
+            .params(vec![json!(address)])
+            .returns(json!({
+                "pending": { "7": pooled(7), "8": pooled(8) },
+                "queued": {},
+            }));
+
+        let content = block_on(txpool.content()).unwrap();
+        let transaction = &content.pending[&sender][&7];
+        assert_eq!(transaction.nonce, Uint64::from(7_u64));
+        assert!(transaction.block_hash.is_none());
+        assert!(transaction.block_number.is_none());
+        assert!(transaction.transaction_index.is_none());
+        assert_eq!(content.queued[&sender][&9].nonce, Uint64::from(9_u64));
+
+        let content = block_on(txpool.content_from(sender.clone())).unwrap();
+        let nonces: Vec<_> = content.pending.keys().copied().collect();
+        assert_eq!(nonces, vec![7, 8]);
+        assert!(content.queued.is_empty());
+        assert_eq!(mock.remaining(), 0);
+    }
+}
@@ -0,0 +1,68 @@
+use std::future::Future;
+
+use w3b_types::{Bytes, Bytes32};
+
+use super::execute;
+use crate::{error::Error, namespace::Namespace, provider::Provider};
+
+/// The `web3_` namespace, named apart from the [`Web3`](crate::Web3) client.
+#[derive(Clone)]
+pub struct Web3Api<T: Provider> {
+    provider: T,
+}
+
+impl<T: Provider> Web3Api<T> {
+    pub fn client_version(&self) -> impl Future<Output = Result<String, Error>> {
+        execute(&self.provider, "web3_clientVersion", vec![])
+    }
+
+    /// Returns the Keccak-256 hash of the data, as computed by the node.
+    pub fn sha3(&self, data: impl Into<Bytes>) -> impl Future<Output = Result<Bytes32, Error>> {
+        let data = serde_json::to_value(data.into()).unwrap();
+        execute(&self.provider, "web3_sha3", vec![data])
+    }
+}
+
+impl<T: Provider> Namespace<T> for Web3Api<T> {
+    #[inline]
+    fn new(provider: T) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    fn provider(&self) -> &T {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::Web3Api;
+    use crate::{namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn client_version_and_sha3() {
+        let mock = MockProvider::new();
+        let web3 = Web3Api::new(mock.clone());
+        let hash = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+        mock.expect("web3_clientVersion")
+            .returns("Geth/v1.14.0-stable/linux-amd64/go1.22.2");
+        mock.expect("web3_sha3")
+            .params(vec![json!("0x")])
+            .returns(hash);
+
+        assert_eq!(
+            block_on(web3.client_version()).unwrap(),
+            "Geth/v1.14.0-stable/linux-amd64/go1.22.2",
+        );
+        assert_eq!(
+            block_on(web3.sha3(Bytes::new(vec![]))).unwrap(),
+            Bytes32::from_hex(hash).unwrap(),
+        );
+    }
+}
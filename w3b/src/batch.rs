@@ -0,0 +1,129 @@
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures::channel::oneshot;
+
+use super::{api::*, error::Error, json_rpc::Request, namespace::Namespace, provider::Provider};
+
+type Queue = Vec<(Request, oneshot::Sender<serde_json::Value>)>;
+
+/// Queues calls made through its namespaces until [`Batch::execute`] sends them all at once.
+///
+/// The future of each queued call resolves once the batch has been executed. If the batch fails
+/// as a whole, its error is returned by [`Batch::execute`] and every queued call is canceled.
+#[derive(Clone)]
+pub struct Batch<T: Provider> {
+    provider: T,
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl<T: Provider> Batch<T> {
+    #[inline]
+    pub fn new(provider: T) -> Self {
+        Self {
+            provider,
+            queue: Default::default(),
+        }
+    }
+
+    pub fn namespace<N: Namespace<Self>>(&self) -> N {
+        N::new(self.clone())
+    }
+
+    pub fn eth(&self) -> eth::Eth<Self> {
+        self.namespace()
+    }
+
+    pub async fn execute(&self) -> Result<(), Error> {
+        let queue = mem::take(&mut *self.queue.lock().unwrap());
+
+        if queue.is_empty() {
+            return Ok(());
+        }
+
+        let (requests, senders): (Vec<_>, Vec<_>) = queue.into_iter().unzip();
+        let responses = self.provider.send_batch(requests).await?;
+
+        for (sender, response) in senders.into_iter().zip(responses) {
+            let _ = sender.send(response);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Provider> Provider for Batch<T> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse = T::BatchResponse;
+
+    fn send(&self, request: Request) -> Self::Response {
+        let (sender, receiver) = oneshot::channel();
+        self.queue.lock().unwrap().push((request, sender));
+        Box::pin(async move { Ok(receiver.await?) })
+    }
+
+    #[inline]
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        self.provider.send_batch(requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use futures::future::{self, Ready};
+    use serde_json::json;
+    use w3b_types::{Address, BlockNumber};
+
+    use super::Batch;
+    use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+    /// Answers every request with its block number parameter.
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Provider for Echo {
+        type Response = Ready<Result<serde_json::Value, Error>>;
+        type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+        fn send(&self, _: Request) -> Self::Response {
+            unreachable!()
+        }
+
+        fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+            let requests = serde_json::to_value(requests).unwrap();
+
+            let responses = requests
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|request| json!({ "jsonrpc": "2.0", "id": null, "result": request["params"][1] }))
+                .collect();
+
+            future::ready(Ok(responses))
+        }
+    }
+
+    fn resolve<T>(future: impl Future<Output = T>) -> T {
+        futures::executor::block_on(future)
+    }
+
+    #[test]
+    fn execute() {
+        let batch = Batch::new(Echo);
+        let address = Address::new([0; 20]);
+
+        let first = batch.eth().balance(address.clone(), BlockNumber::Number(1));
+        let second = batch.eth().balance(address, BlockNumber::Number(2));
+
+        resolve(batch.execute()).unwrap();
+
+        assert_eq!(resolve(first).unwrap(), 1_u64.into());
+        assert_eq!(resolve(second).unwrap(), 2_u64.into());
+    }
+}
@@ -0,0 +1,570 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use tokio::time::delay_for;
+use w3b_types::{BlockNumber, Bytes32, Filter, FilterBlocks, Header, Log};
+
+use super::{
+    api::eth::Eth,
+    error::Error,
+    provider::{Provider, PubSubProvider},
+};
+
+#[derive(Clone, Debug)]
+pub enum BlockEvent {
+    /// The block joined the canonical chain.
+    Applied(Header),
+    /// The block left the canonical chain in a reorg.
+    Reverted(Header),
+}
+
+/// Follows the chain head, tracking the parent hashes of recent blocks to report reorgs.
+///
+/// When a new head does not build on the tracked chain, its missing ancestors are fetched by
+/// hash until they connect, and the orphaned blocks are reported as reverted, from the highest,
+/// before the new ones are reported as applied, from the lowest. Heads that skip blocks have the
+/// skipped blocks applied in between, however many. A fork deeper than the tracked depth is
+/// reported as an error, after which the tracking restarts from the next head.
+pub struct BlockStream {
+    inner: BoxStream<'static, Result<BlockEvent, Error>>,
+    depth: usize,
+}
+
+impl BlockStream {
+    /// Number of recent blocks tracked by default.
+    pub const DEPTH: usize = 64;
+
+    /// Follows the given heads, fetching missing ancestors through `eth`, and keeps the last
+    /// `depth` blocks.
+    pub fn new<T: Provider>(
+        eth: &Eth<T>,
+        heads: impl Stream<Item = Result<Header, Error>> + Send + 'static,
+        depth: usize,
+    ) -> Self {
+        let tracker = Tracker {
+            eth: eth.clone(),
+            chain: VecDeque::new(),
+            depth: depth.max(1),
+            pruned: false,
+        };
+
+        let state = (tracker, heads.boxed(), VecDeque::new());
+        let inner = stream::unfold(state, |(mut tracker, mut heads, mut buffer)| async move {
+            loop {
+                if let Some(event) = buffer.pop_front() {
+                    return Some((Ok(event), (tracker, heads, buffer)));
+                }
+
+                let result = match heads.next().await? {
+                    Ok(head) => tracker.apply(head).await,
+                    Err(error) => Err(error),
+                };
+
+                match result {
+                    Ok(events) => buffer.extend(events),
+                    Err(error) => return Some((Err(error), (tracker, heads, buffer))),
+                }
+            }
+        });
+
+        Self {
+            inner: inner.boxed(),
+            depth: depth.max(1),
+        }
+    }
+
+    /// Follows the head by polling the latest block every `interval`.
+    pub fn polling<T: Provider>(eth: &Eth<T>, interval: Duration) -> Self {
+        let heads = stream::unfold((eth.clone(), true), move |(eth, first)| async move {
+            if !first {
+                delay_for(interval).await;
+            }
+
+            let head = eth
+                .block(BlockNumber::Latest)
+                .await
+                .and_then(|block| block.map(|block| block.header).ok_or_else(missing_block));
+
+            Some((head, (eth, false)))
+        });
+
+        Self::new(eth, heads, Self::DEPTH)
+    }
+
+    /// Follows the head through a `newHeads` subscription.
+    pub async fn subscribe<T: PubSubProvider>(eth: &Eth<T>) -> Result<Self, Error> {
+        let heads = eth.subscribe_new_heads().await?;
+        Ok(Self::new(eth, heads, Self::DEPTH))
+    }
+
+    /// Streams the logs matching the filter in the applied blocks, and the same logs again with
+    /// `removed` set when their blocks are reverted, ignoring the filter's blocks.
+    ///
+    /// Failing to fetch the logs of a block is reported as an error, and the fetch is retried when
+    /// the stream is polled again, before moving on to the next block.
+    pub fn logs<T: Provider>(self, eth: &Eth<T>, filter: impl Into<Filter>) -> LogStream {
+        let depth = self.depth;
+        let state = LogState {
+            eth: eth.clone(),
+            blocks: self,
+            filter: filter.into(),
+            logs: HashMap::new(),
+            order: VecDeque::new(),
+            depth,
+            buffer: VecDeque::new(),
+            pending: None,
+        };
+
+        let inner = stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        });
+
+        LogStream {
+            inner: inner.boxed(),
+        }
+    }
+}
+
+impl Stream for BlockStream {
+    type Item = Result<BlockEvent, Error>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Streams the logs of a [`BlockStream`], see [`BlockStream::logs`].
+pub struct LogStream {
+    inner: BoxStream<'static, Result<Log, Error>>,
+}
+
+impl Stream for LogStream {
+    type Item = Result<Log, Error>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+struct Tracker<T: Provider> {
+    eth: Eth<T>,
+    /// Recent canonical blocks, from the lowest.
+    chain: VecDeque<Header>,
+    depth: usize,
+    /// Whether blocks were applied before the tracked ones.
+    pruned: bool,
+}
+
+impl<T: Provider> Tracker<T> {
+    async fn apply(&mut self, head: Header) -> Result<Vec<BlockEvent>, Error> {
+        let hash = match &head.hash {
+            Some(hash) => hash.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        if self
+            .chain
+            .iter()
+            .any(|block| block.hash.as_ref() == Some(&hash))
+        {
+            return Ok(Vec::new());
+        }
+
+        // New blocks from the highest, and the number of tracked blocks they build on. Nothing is
+        // changed until all the missing ancestors are fetched, so that errors leave the chain as
+        // it was.
+        let mut applied = vec![head];
+        let mut kept = self.chain.len();
+
+        loop {
+            let lowest = applied.last().unwrap();
+
+            while kept > 0 && number(&self.chain[kept - 1]) >= number(lowest) {
+                kept -= 1;
+            }
+
+            if self.chain.is_empty()
+                || (kept > 0 && self.chain[kept - 1].hash.as_ref() == Some(&lowest.parent_hash))
+            {
+                break;
+            }
+
+            // Every tracked block was reverted without finding the common ancestor, which is only
+            // fine if no block was applied before them.
+            if kept == 0 {
+                if !self.pruned {
+                    break;
+                }
+
+                self.chain.clear();
+                self.pruned = false;
+                return Err(Error::Provider(
+                    format!("reorg deeper than the {} tracked blocks", self.depth).into(),
+                ));
+            }
+
+            let parent = self
+                .eth
+                .block(lowest.parent_hash.clone())
+                .await?
+                .ok_or_else(missing_block)?;
+            applied.push(parent.header);
+        }
+
+        let mut events: Vec<_> = self
+            .chain
+            .drain(kept..)
+            .rev()
+            .map(BlockEvent::Reverted)
+            .collect();
+
+        for block in applied.into_iter().rev() {
+            self.chain.push_back(block.clone());
+            events.push(BlockEvent::Applied(block));
+        }
+
+        while self.chain.len() > self.depth {
+            self.chain.pop_front();
+            self.pruned = true;
+        }
+
+        Ok(events)
+    }
+}
+
+struct LogState<T: Provider> {
+    eth: Eth<T>,
+    blocks: BlockStream,
+    filter: Filter,
+    /// Logs emitted for the tracked blocks, by block hash.
+    logs: HashMap<Bytes32, Vec<Log>>,
+    order: VecDeque<Bytes32>,
+    depth: usize,
+    buffer: VecDeque<Log>,
+    /// Applied block whose logs failed to be fetched, to retry before the next event.
+    pending: Option<Bytes32>,
+}
+
+impl<T: Provider> LogState<T> {
+    async fn next(&mut self) -> Option<Result<Log, Error>> {
+        loop {
+            if let Some(log) = self.buffer.pop_front() {
+                return Some(Ok(log));
+            }
+
+            let hash = match self.pending.take() {
+                Some(hash) => hash,
+
+                None => match self.blocks.next().await? {
+                    Ok(BlockEvent::Applied(Header {
+                        hash: Some(hash), ..
+                    })) => hash,
+
+                    Ok(BlockEvent::Reverted(Header {
+                        hash: Some(hash), ..
+                    })) => {
+                        self.order.retain(|tracked| *tracked != hash);
+                        let logs = self.logs.remove(&hash).unwrap_or_default();
+                        self.buffer.extend(logs.into_iter().rev().map(|mut log| {
+                            log.removed = true;
+                            log
+                        }));
+                        continue;
+                    }
+
+                    Ok(_) => continue,
+                    Err(error) => return Some(Err(error)),
+                },
+            };
+
+            let filter = Filter {
+                blocks: FilterBlocks::Hash {
+                    blockhash: hash.clone(),
+                },
+                address: self.filter.address.clone(),
+                topics: self.filter.topics.clone(),
+            };
+
+            let logs = match self.eth.logs(filter).await {
+                Ok(logs) => logs,
+                Err(error) => {
+                    self.pending = Some(hash);
+                    return Some(Err(error));
+                }
+            };
+
+            self.buffer.extend(logs.iter().cloned());
+            self.logs.insert(hash.clone(), logs);
+            self.order.push_back(hash);
+
+            while self.order.len() > self.depth {
+                if let Some(hash) = self.order.pop_front() {
+                    self.logs.remove(&hash);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn number(header: &Header) -> u64 {
+    header.number.clone().map(u64::from).unwrap_or(0)
+}
+
+#[inline]
+fn missing_block() -> Error {
+    Error::Provider("missing block".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{executor::block_on, stream, StreamExt};
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::{BlockEvent, BlockStream};
+    use crate::{api::eth::Eth, error::Error, namespace::Namespace, providers::MockProvider};
+
+    fn hash(byte: u8) -> Bytes32 {
+        Bytes32::from_hex(format!("0x{}", format!("{:02x}", byte).repeat(32))).unwrap()
+    }
+
+    fn block(number: u64, id: u8, parent_id: u8) -> serde_json::Value {
+        let root = format!("0x{}", "ff".repeat(32));
+
+        json!({
+            "hash": hash(id),
+            "parentHash": hash(parent_id),
+            "sha3Uncles": root,
+            "miner": format!("0x{}", "22".repeat(20)),
+            "stateRoot": root,
+            "transactionsRoot": root,
+            "receiptsRoot": root,
+            "logsBloom": "0x00",
+            "difficulty": "0x0",
+            "number": format!("0x{:x}", number),
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x5",
+            "extraData": "0x",
+        })
+    }
+
+    fn heads(blocks: Vec<(u64, u8, u8)>) -> impl futures::Stream<Item = Result<Header, Error>> {
+        stream::iter(blocks.into_iter().map(|(number, id, parent_id)| {
+            Ok(serde_json::from_value(block(number, id, parent_id)).unwrap())
+        }))
+    }
+
+    fn describe(event: Result<BlockEvent, Error>) -> String {
+        let (kind, header) = match event.unwrap() {
+            BlockEvent::Applied(header) => ("applied", header),
+            BlockEvent::Reverted(header) => ("reverted", header),
+        };
+
+        format!("{} {:?}", kind, header.hash.unwrap().as_bytes()[0])
+    }
+
+    #[test]
+    fn follow_competing_forks() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_getBlockByHash")
+            .params(vec![json!(hash(0x12)), json!(false)])
+            .returns(block(2, 0x12, 0x01));
+
+        // 0x01 <- 0x02, then 0x01 <- 0x12 <- 0x13, then 0x01 <- 0x22.
+        let heads = heads(vec![
+            (1, 0x01, 0x00),
+            (2, 0x02, 0x01),
+            (2, 0x02, 0x01),
+            (3, 0x13, 0x12),
+            (2, 0x22, 0x01),
+        ]);
+
+        let events: Vec<_> = block_on(BlockStream::new(&eth, heads, 8).collect());
+        let events: Vec<_> = events.into_iter().map(describe).collect();
+
+        assert_eq!(
+            events,
+            vec![
+                "applied 1",
+                "applied 2",
+                "reverted 2",
+                "applied 18",
+                "applied 19",
+                "reverted 19",
+                "reverted 18",
+                "applied 34",
+            ],
+        );
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn fill_gaps_longer_than_depth() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        for number in (3..10).rev() {
+            mock.expect("eth_getBlockByHash")
+                .params(vec![json!(hash(number)), json!(false)])
+                .returns(block(number.into(), number, number - 1));
+        }
+
+        let heads = heads(vec![(1, 1, 0), (2, 2, 1), (10, 10, 9)]);
+        let events: Vec<_> = block_on(BlockStream::new(&eth, heads, 4).collect());
+        let events: Vec<_> = events.into_iter().map(describe).collect();
+
+        let expected: Vec<_> = (1..=10)
+            .map(|number| format!("applied {}", number))
+            .collect();
+        assert_eq!(events, expected);
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn fail_on_forks_deeper_than_depth() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_getBlockByHash")
+            .params(vec![json!(hash(0x32)), json!(false)])
+            .returns(block(2, 0x32, 0x31));
+
+        // 1 <- 2 <- 3 with the last two tracked, then 0x31 <- 0x32 <- 0x33.
+        let heads = heads(vec![
+            (1, 1, 0),
+            (2, 2, 1),
+            (3, 3, 2),
+            (3, 0x33, 0x32),
+            (4, 4, 3),
+        ]);
+        let events: Vec<_> = block_on(BlockStream::new(&eth, heads, 2).collect());
+
+        assert!(events[3].is_err());
+        let events: Vec<_> = events
+            .into_iter()
+            .filter(Result::is_ok)
+            .map(describe)
+            .collect();
+        assert_eq!(
+            events,
+            vec!["applied 1", "applied 2", "applied 3", "applied 4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn poll_latest_block() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        for (number, id, parent_id) in [(1, 0x01, 0x00), (1, 0x01, 0x00), (2, 0x02, 0x01)] {
+            mock.expect("eth_getBlockByNumber")
+                .params(vec![json!("latest"), json!(false)])
+                .returns(block(number, id, parent_id));
+        }
+
+        let mut blocks = BlockStream::polling(&eth, Duration::from_millis(1));
+        assert_eq!(describe(blocks.next().await.unwrap()), "applied 1");
+        assert_eq!(describe(blocks.next().await.unwrap()), "applied 2");
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn revert_logs_of_orphaned_blocks() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        let filter = |hash| json!({ "blockhash": hash, "address": null, "topics": [] });
+        let log = json!({
+            "address": format!("0x{}", "11".repeat(20)),
+            "topics": [],
+            "data": "0x",
+            "blockHash": hash(0x01),
+            "removed": false,
+        });
+
+        mock.expect("eth_getLogs")
+            .params(vec![filter(hash(0x01))])
+            .returns(json!([log]));
+        mock.expect("eth_getLogs")
+            .params(vec![filter(hash(0x11))])
+            .returns(json!([]));
+
+        let blocks = BlockStream::new(&eth, heads(vec![(1, 0x01, 0x00), (1, 0x11, 0x00)]), 8);
+        let filter = Filter {
+            blocks: FilterBlocks::Range {
+                from_block: None,
+                to_block: None,
+            },
+            address: None,
+            topics: Vec::new(),
+        };
+
+        let logs: Vec<_> = block_on(blocks.logs(&eth, filter).collect());
+        let logs: Vec<_> = logs
+            .into_iter()
+            .map(|log| {
+                let log = log.unwrap();
+                (log.block_hash.unwrap(), log.removed)
+            })
+            .collect();
+
+        assert_eq!(logs, vec![(hash(0x01), false), (hash(0x01), true)]);
+    }
+
+    #[test]
+    fn retry_failed_log_fetches() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        let filter = json!({ "blockhash": hash(0x01), "address": null, "topics": [] });
+        let log = json!({
+            "address": format!("0x{}", "11".repeat(20)),
+            "topics": [],
+            "data": "0x",
+            "blockHash": hash(0x01),
+            "removed": false,
+        });
+
+        mock.expect("eth_getLogs")
+            .params(vec![filter.clone()])
+            .fails("connection reset");
+        mock.expect("eth_getLogs")
+            .params(vec![filter])
+            .returns(json!([log]));
+        mock.expect("eth_getLogs").returns(json!([]));
+
+        let blocks = BlockStream::new(&eth, heads(vec![(1, 0x01, 0x00), (1, 0x11, 0x00)]), 8);
+        let filter = Filter {
+            blocks: FilterBlocks::Range {
+                from_block: None,
+                to_block: None,
+            },
+            address: None,
+            topics: Vec::new(),
+        };
+
+        let logs: Vec<_> = block_on(blocks.logs(&eth, filter).collect());
+        assert!(logs[0].is_err());
+
+        let logs: Vec<_> = logs[1..]
+            .iter()
+            .map(|log| log.as_ref().unwrap().removed)
+            .collect();
+        assert_eq!(logs, vec![false, true]);
+        assert_eq!(mock.remaining(), 0);
+    }
+}
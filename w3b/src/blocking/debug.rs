@@ -0,0 +1,49 @@
+use w3b_types::*;
+
+use super::Executor;
+use crate::{
+    api::debug::{self, Tracer},
+    error::Error,
+    provider::Provider,
+};
+
+#[derive(Clone)]
+pub struct Debug<T: Provider> {
+    debug: debug::Debug<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Debug<T> {
+    #[inline]
+    pub(super) fn new(debug: debug::Debug<T>, executor: Executor) -> Self {
+        Self { debug, executor }
+    }
+
+    pub fn trace_transaction<R: Tracer>(
+        &self,
+        hash: impl Into<Bytes32>,
+        tracer: &R,
+    ) -> Result<R::Output, Error> {
+        self.executor
+            .block_on(self.debug.trace_transaction(hash, tracer))
+    }
+
+    pub fn trace_call<R: Tracer>(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+        tracer: &R,
+    ) -> Result<R::Output, Error> {
+        self.executor
+            .block_on(self.debug.trace_call(call, block_id, tracer))
+    }
+
+    pub fn trace_block_by_number<R: Tracer>(
+        &self,
+        block_number: impl Into<BlockNumber>,
+        tracer: &R,
+    ) -> Result<Vec<BlockTrace<R::Output>>, Error> {
+        self.executor
+            .block_on(self.debug.trace_block_by_number(block_number, tracer))
+    }
+}
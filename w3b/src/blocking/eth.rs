@@ -0,0 +1,238 @@
+use serde::de::DeserializeOwned;
+use w3b_types::*;
+
+use super::Executor;
+use crate::{api::eth, error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct Eth<T: Provider> {
+    eth: eth::Eth<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Eth<T> {
+    #[inline]
+    pub(super) fn new(eth: eth::Eth<T>, executor: Executor) -> Self {
+        Self { eth, executor }
+    }
+
+    pub fn block_number(&self) -> Result<u64, Error> {
+        self.executor.block_on(self.eth.block_number())
+    }
+
+    pub fn balance(
+        &self,
+        address: impl Into<Address>,
+        block_number: impl Into<Option<BlockNumber>>,
+    ) -> Result<Uint256, Error> {
+        self.executor
+            .block_on(self.eth.balance(address, block_number))
+    }
+
+    pub fn logs(&self, filter: impl Into<Filter>) -> Result<Vec<Log>, Error> {
+        self.executor.block_on(self.eth.logs(filter))
+    }
+
+    pub fn transaction_count(
+        &self,
+        address: impl Into<Address>,
+        block_number: impl Into<Option<BlockNumber>>,
+    ) -> Result<Uint64, Error> {
+        self.executor
+            .block_on(self.eth.transaction_count(address, block_number))
+    }
+
+    pub fn block(&self, block_id: impl Into<BlockId>) -> Result<Option<Block>, Error> {
+        self.executor.block_on(self.eth.block(block_id))
+    }
+
+    pub fn block_with_transactions(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> Result<Option<Block<Transaction>>, Error> {
+        self.executor
+            .block_on(self.eth.block_with_transactions(block_id))
+    }
+
+    pub fn uncle(&self, block_id: impl Into<BlockId>, index: u64) -> Result<Option<Block>, Error> {
+        self.executor.block_on(self.eth.uncle(block_id, index))
+    }
+
+    pub fn uncle_count(&self, block_id: impl Into<BlockId>) -> Result<u64, Error> {
+        self.executor.block_on(self.eth.uncle_count(block_id))
+    }
+
+    pub fn transaction(&self, hash: impl Into<Bytes32>) -> Result<Option<Transaction>, Error> {
+        self.executor.block_on(self.eth.transaction(hash))
+    }
+
+    pub fn transaction_by_block_and_index(
+        &self,
+        block_id: impl Into<BlockId>,
+        index: u64,
+    ) -> Result<Option<Transaction>, Error> {
+        self.executor
+            .block_on(self.eth.transaction_by_block_and_index(block_id, index))
+    }
+
+    pub fn transaction_receipt(
+        &self,
+        hash: impl Into<Bytes32>,
+    ) -> Result<Option<TransactionReceipt>, Error> {
+        self.executor.block_on(self.eth.transaction_receipt(hash))
+    }
+
+    pub fn block_receipts(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> Result<Option<Vec<TransactionReceipt>>, Error> {
+        self.executor.block_on(self.eth.block_receipts(block_id))
+    }
+
+    pub fn call(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> Result<Bytes, Error> {
+        self.executor.block_on(self.eth.call(call, block_id))
+    }
+
+    pub fn call_with_state_override(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+        state_override: &StateOverride,
+    ) -> Result<Bytes, Error> {
+        self.executor.block_on(
+            self.eth
+                .call_with_state_override(call, block_id, state_override),
+        )
+    }
+
+    pub fn estimate_gas(
+        &self,
+        call: &CallRequest,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> Result<u64, Error> {
+        self.executor
+            .block_on(self.eth.estimate_gas(call, block_id))
+    }
+
+    pub fn send_transaction(&self, transaction: &TransactionRequest) -> Result<Bytes32, Error> {
+        self.executor
+            .block_on(self.eth.send_transaction(transaction))
+    }
+
+    pub fn send_raw_transaction(&self, transaction: impl Into<Bytes>) -> Result<Bytes32, Error> {
+        self.executor
+            .block_on(self.eth.send_raw_transaction(transaction))
+    }
+
+    pub fn gas_price(&self) -> Result<Uint256, Error> {
+        self.executor.block_on(self.eth.gas_price())
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> Result<Uint256, Error> {
+        self.executor.block_on(self.eth.max_priority_fee_per_gas())
+    }
+
+    pub fn blob_base_fee(&self) -> Result<Uint256, Error> {
+        self.executor.block_on(self.eth.blob_base_fee())
+    }
+
+    pub fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: impl Into<BlockNumber>,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, Error> {
+        self.executor.block_on(
+            self.eth
+                .fee_history(block_count, newest_block, reward_percentiles),
+        )
+    }
+
+    pub fn code(
+        &self,
+        address: impl Into<Address>,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> Result<Bytes, Error> {
+        self.executor.block_on(self.eth.code(address, block_id))
+    }
+
+    pub fn storage_at(
+        &self,
+        address: impl Into<Address>,
+        slot: impl Into<Uint256>,
+        block_id: impl Into<Option<BlockId>>,
+    ) -> Result<Bytes32, Error> {
+        self.executor
+            .block_on(self.eth.storage_at(address, slot, block_id))
+    }
+
+    pub fn proof(
+        &self,
+        address: impl Into<Address>,
+        storage_keys: &[Uint256],
+        block_id: impl Into<Option<BlockId>>,
+    ) -> Result<AccountProof, Error> {
+        self.executor
+            .block_on(self.eth.proof(address, storage_keys, block_id))
+    }
+
+    pub fn new_filter(&self, filter: impl Into<Filter>) -> Result<FilterId, Error> {
+        self.executor.block_on(self.eth.new_filter(filter))
+    }
+
+    pub fn new_block_filter(&self) -> Result<FilterId, Error> {
+        self.executor.block_on(self.eth.new_block_filter())
+    }
+
+    pub fn new_pending_transaction_filter(&self) -> Result<FilterId, Error> {
+        self.executor
+            .block_on(self.eth.new_pending_transaction_filter())
+    }
+
+    pub fn filter_changes<U: DeserializeOwned>(&self, id: &FilterId) -> Result<Vec<U>, Error> {
+        self.executor.block_on(self.eth.filter_changes(id))
+    }
+
+    pub fn filter_logs(&self, id: &FilterId) -> Result<Vec<Log>, Error> {
+        self.executor.block_on(self.eth.filter_logs(id))
+    }
+
+    pub fn uninstall_filter(&self, id: &FilterId) -> Result<bool, Error> {
+        self.executor.block_on(self.eth.uninstall_filter(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use w3b_types::{Address, BlockNumber};
+
+    use crate::{blocking::Web3, providers::MockProvider};
+
+    #[test]
+    fn call_without_runtime() {
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber").returns("0x2a");
+        mock.expect("eth_getTransactionCount")
+            .params(vec![
+                json!(format!("0x{}", "0".repeat(40))),
+                json!("latest"),
+            ])
+            .returns("0x7");
+
+        let web3 = Web3::new(mock).unwrap();
+        let eth = web3.eth();
+
+        assert_eq!(eth.block_number().unwrap(), 42);
+
+        let nonce: u64 = eth
+            .transaction_count(Address::new([0; 20]), BlockNumber::Latest)
+            .unwrap()
+            .into();
+        assert_eq!(nonce, 7);
+    }
+}
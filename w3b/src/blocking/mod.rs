@@ -0,0 +1,50 @@
+//! Synchronous mirrors of [`Web3`](crate::Web3) and its namespaces, for callers without an async
+//! runtime of their own.
+//!
+//! Calls are driven to completion on an internal current-thread runtime, so they must not be made
+//! from within an async context.
+
+mod debug;
+mod eth;
+mod net;
+mod trace;
+mod txpool;
+mod web3;
+mod web3_api;
+
+pub use debug::*;
+pub use eth::*;
+pub use net::*;
+pub use trace::*;
+pub use txpool::*;
+pub use web3::*;
+pub use web3_api::*;
+
+use std::{
+    future::Future,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use tokio::runtime::{self, Runtime};
+
+/// A current-thread runtime shared between a blocking client and its namespaces, running one
+/// call at a time.
+#[derive(Clone)]
+struct Executor(Arc<Mutex<Runtime>>);
+
+impl Executor {
+    fn new() -> io::Result<Self> {
+        let runtime = runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()?;
+
+        Ok(Self(Arc::new(Mutex::new(runtime))))
+    }
+
+    #[inline]
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.0.lock().unwrap().block_on(future)
+    }
+}
@@ -0,0 +1,27 @@
+use super::Executor;
+use crate::{api::net, error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct Net<T: Provider> {
+    net: net::Net<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Net<T> {
+    #[inline]
+    pub(super) fn new(net: net::Net<T>, executor: Executor) -> Self {
+        Self { net, executor }
+    }
+
+    pub fn version(&self) -> Result<u64, Error> {
+        self.executor.block_on(self.net.version())
+    }
+
+    pub fn peer_count(&self) -> Result<u64, Error> {
+        self.executor.block_on(self.net.peer_count())
+    }
+
+    pub fn listening(&self) -> Result<bool, Error> {
+        self.executor.block_on(self.net.listening())
+    }
+}
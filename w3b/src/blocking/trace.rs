@@ -0,0 +1,41 @@
+use w3b_types::*;
+
+use super::Executor;
+use crate::{api::trace, error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct Trace<T: Provider> {
+    trace: trace::Trace<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Trace<T> {
+    #[inline]
+    pub(super) fn new(trace: trace::Trace<T>, executor: Executor) -> Self {
+        Self { trace, executor }
+    }
+
+    pub fn transaction(&self, hash: impl Into<Bytes32>) -> Result<Vec<TransactionTrace>, Error> {
+        self.executor.block_on(self.trace.transaction(hash))
+    }
+
+    pub fn block(
+        &self,
+        block_number: impl Into<BlockNumber>,
+    ) -> Result<Option<Vec<TransactionTrace>>, Error> {
+        self.executor.block_on(self.trace.block(block_number))
+    }
+
+    pub fn filter(&self, filter: &TraceFilter) -> Result<Vec<TransactionTrace>, Error> {
+        self.executor.block_on(self.trace.filter(filter))
+    }
+
+    pub fn replay_transaction(
+        &self,
+        hash: impl Into<Bytes32>,
+        trace_types: &[TraceType],
+    ) -> Result<TraceResults, Error> {
+        self.executor
+            .block_on(self.trace.replay_transaction(hash, trace_types))
+    }
+}
@@ -0,0 +1,33 @@
+use w3b_types::*;
+
+use super::Executor;
+use crate::{api::txpool, error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct TxPool<T: Provider> {
+    txpool: txpool::TxPool<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> TxPool<T> {
+    #[inline]
+    pub(super) fn new(txpool: txpool::TxPool<T>, executor: Executor) -> Self {
+        Self { txpool, executor }
+    }
+
+    pub fn status(&self) -> Result<TxPoolStatus, Error> {
+        self.executor.block_on(self.txpool.status())
+    }
+
+    pub fn content(&self) -> Result<TxPoolContent, Error> {
+        self.executor.block_on(self.txpool.content())
+    }
+
+    pub fn inspect(&self) -> Result<TxPoolInspect, Error> {
+        self.executor.block_on(self.txpool.inspect())
+    }
+
+    pub fn content_from(&self, address: impl Into<Address>) -> Result<TxPoolContentFrom, Error> {
+        self.executor.block_on(self.txpool.content_from(address))
+    }
+}
@@ -0,0 +1,45 @@
+use super::{
+    debug::Debug, eth::Eth, net::Net, trace::Trace, txpool::TxPool, web3_api::Web3Api, Executor,
+};
+use crate::{error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct Web3<T: Provider> {
+    web3: crate::web3::Web3<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Web3<T> {
+    pub fn new(provider: T) -> Result<Self, Error> {
+        Ok(Self {
+            web3: crate::web3::Web3::new(provider),
+            executor: Executor::new()?,
+        })
+    }
+}
+
+impl<T: Provider> Web3<T> {
+    pub fn eth(&self) -> Eth<T> {
+        Eth::new(self.web3.eth(), self.executor.clone())
+    }
+
+    pub fn net(&self) -> Net<T> {
+        Net::new(self.web3.net(), self.executor.clone())
+    }
+
+    pub fn web3(&self) -> Web3Api<T> {
+        Web3Api::new(self.web3.web3(), self.executor.clone())
+    }
+
+    pub fn txpool(&self) -> TxPool<T> {
+        TxPool::new(self.web3.txpool(), self.executor.clone())
+    }
+
+    pub fn debug(&self) -> Debug<T> {
+        Debug::new(self.web3.debug(), self.executor.clone())
+    }
+
+    pub fn trace(&self) -> Trace<T> {
+        Trace::new(self.web3.trace(), self.executor.clone())
+    }
+}
@@ -0,0 +1,25 @@
+use w3b_types::{Bytes, Bytes32};
+
+use super::Executor;
+use crate::{api::web3_api, error::Error, provider::Provider};
+
+#[derive(Clone)]
+pub struct Web3Api<T: Provider> {
+    web3: web3_api::Web3Api<T>,
+    executor: Executor,
+}
+
+impl<T: Provider> Web3Api<T> {
+    #[inline]
+    pub(super) fn new(web3: web3_api::Web3Api<T>, executor: Executor) -> Self {
+        Self { web3, executor }
+    }
+
+    pub fn client_version(&self) -> Result<String, Error> {
+        self.executor.block_on(self.web3.client_version())
+    }
+
+    pub fn sha3(&self, data: impl Into<Bytes>) -> Result<Bytes32, Error> {
+        self.executor.block_on(self.web3.sha3(data))
+    }
+}
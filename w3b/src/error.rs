@@ -1,8 +1,50 @@
-use std::{error::Error as StdError, fmt};
+use std::{error::Error as StdError, fmt, io, time::Duration};
+
+use w3b_types::Bytes;
+
+use super::json_rpc::ResponseError;
 
 pub enum Error {
     Codec(serde_json::Error),
-    Provider(Box<dyn StdError>),
+    Provider(Box<dyn StdError + Send + Sync>),
+    Status {
+        status: u16,
+        retry_after: Option<Duration>,
+    },
+    IdMismatch {
+        expected: serde_json::Value,
+        actual: serde_json::Value,
+    },
+    Rpc {
+        code: i64,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+    /// A call reverted, with the raw revert data, e.g. an ABI-encoded `Error(string)`.
+    Revert(Bytes),
+}
+
+impl Error {
+    /// Recognizes the errors nodes answer reverted calls with, by their code `3` or their
+    /// `execution reverted` message, carrying the revert data as a hex string when there is any.
+    pub(crate) fn into_revert(self) -> Self {
+        match &self {
+            Error::Rpc {
+                code,
+                message,
+                data,
+            } if *code == 3 || message.starts_with("execution reverted") => match data {
+                Some(serde_json::Value::String(data)) => match Bytes::from_hex(data) {
+                    Ok(data) => Error::Revert(data),
+                    Err(_) => self,
+                },
+                None => Error::Revert(Bytes::new(vec![])),
+                Some(_) => self,
+            },
+
+            _ => self,
+        }
+    }
 }
 
 impl fmt::Debug for Error {
@@ -11,6 +53,36 @@ impl fmt::Debug for Error {
         match self {
             Error::Codec(error) => error.fmt(f),
             Error::Provider(error) => error.fmt(f),
+
+            Error::Status {
+                status,
+                retry_after: None,
+            } => write!(f, "HTTP status {}", status),
+
+            Error::Status {
+                status,
+                retry_after: Some(retry_after),
+            } => write!(f, "HTTP status {}, retry after {:?}", status, retry_after),
+
+            Error::IdMismatch { expected, actual } => write!(
+                f,
+                "response id mismatch at {}, expected {}",
+                actual, expected,
+            ),
+
+            Error::Rpc {
+                code,
+                message,
+                data: None,
+            } => write!(f, "JSON-RPC error {}: {}", code, message),
+
+            Error::Rpc {
+                code,
+                message,
+                data: Some(data),
+            } => write!(f, "JSON-RPC error {}: {} ({})", code, message, data),
+
+            Error::Revert(data) => write!(f, "execution reverted with {}", data.to_hex()),
         }
     }
 }
@@ -31,9 +103,55 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<ResponseError> for Error {
+    #[inline]
+    fn from(error: ResponseError) -> Self {
+        Error::Rpc {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        }
+    }
+}
+
 impl From<reqwest::Error> for Error {
     #[inline]
     fn from(error: reqwest::Error) -> Self {
         Error::Provider(Box::new(error))
     }
 }
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Error::Provider(Box::new(error))
+    }
+}
+
+impl From<native_tls::Error> for Error {
+    #[inline]
+    fn from(error: native_tls::Error) -> Self {
+        Error::Provider(Box::new(error))
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    #[inline]
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::Provider(Box::new(error))
+    }
+}
+
+impl From<futures::channel::oneshot::Canceled> for Error {
+    #[inline]
+    fn from(error: futures::channel::oneshot::Canceled) -> Self {
+        Error::Provider(Box::new(error))
+    }
+}
+
+impl From<futures::channel::mpsc::SendError> for Error {
+    #[inline]
+    fn from(error: futures::channel::mpsc::SendError) -> Self {
+        Error::Provider(Box::new(error))
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{ready, Stream};
+use serde::de::DeserializeOwned;
+use tokio::time::{delay_for, Delay};
+use w3b_types::{Filter, FilterId};
+
+use super::{api::eth::Eth, error::Error, provider::Provider};
+
+type Changes<U> = Pin<Box<dyn Future<Output = (Option<FilterId>, Result<Vec<U>, Error>)> + Send>>;
+
+#[derive(Clone)]
+pub(crate) enum FilterKind {
+    Logs(Filter),
+    Blocks,
+    PendingTransactions,
+}
+
+/// Streams what a node-side filter matches, polling it on an interval.
+///
+/// The filter is installed on the first poll and installed again whenever the node reports it
+/// as unknown, as nodes drop filters that are not polled for a while or when they restart. What
+/// the filter matched between the two installations is missed.
+pub struct FilterWatcher<T: Provider, U> {
+    eth: Eth<T>,
+    kind: FilterKind,
+    interval: Duration,
+    id: Option<FilterId>,
+    delay: Option<Delay>,
+    changes: Option<Changes<U>>,
+    buffer: VecDeque<U>,
+}
+
+impl<T: Provider, U: DeserializeOwned + Send + 'static> FilterWatcher<T, U> {
+    #[inline]
+    pub(crate) fn new(eth: Eth<T>, kind: FilterKind, interval: Duration) -> Self {
+        Self {
+            eth,
+            kind,
+            interval,
+            id: None,
+            delay: None,
+            changes: None,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Returns the id of the installed filter, if any.
+    #[inline]
+    pub fn id(&self) -> Option<&FilterId> {
+        self.id.as_ref()
+    }
+
+    /// Removes the filter from the node, returning whether it was installed.
+    pub async fn uninstall(self) -> Result<bool, Error> {
+        match self.id {
+            Some(id) => self.eth.uninstall_filter(&id).await,
+            None => Ok(false),
+        }
+    }
+
+    fn poll_changes(&self) -> Changes<U> {
+        let eth = self.eth.clone();
+        let kind = self.kind.clone();
+        let id = self.id.clone();
+
+        Box::pin(async move {
+            let id = match id {
+                Some(id) => id,
+                None => match install(&eth, &kind).await {
+                    Ok(id) => id,
+                    Err(error) => return (None, Err(error)),
+                },
+            };
+
+            match eth.filter_changes(&id).await {
+                Ok(changes) => (Some(id), Ok(changes)),
+                Err(error) if is_filter_not_found(&error) => match install(&eth, &kind).await {
+                    Ok(id) => (Some(id), Ok(Vec::new())),
+                    Err(error) => (None, Err(error)),
+                },
+                Err(error) => (Some(id), Err(error)),
+            }
+        })
+    }
+}
+
+impl<T: Provider, U> Unpin for FilterWatcher<T, U> {}
+
+impl<T: Provider, U: DeserializeOwned + Send + 'static> Stream for FilterWatcher<T, U> {
+    type Item = Result<U, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if let Some(changes) = &mut this.changes {
+                let (id, result) = ready!(changes.as_mut().poll(cx));
+                this.id = id;
+                this.changes = None;
+                this.delay = Some(delay_for(this.interval));
+
+                match result {
+                    Ok(changes) => this.buffer.extend(changes),
+                    Err(error) => return Poll::Ready(Some(Err(error))),
+                }
+
+                continue;
+            }
+
+            if let Some(delay) = &mut this.delay {
+                ready!(Pin::new(delay).poll(cx));
+                this.delay = None;
+            }
+
+            this.changes = Some(this.poll_changes());
+        }
+    }
+}
+
+async fn install<T: Provider>(eth: &Eth<T>, kind: &FilterKind) -> Result<FilterId, Error> {
+    match kind {
+        FilterKind::Logs(filter) => eth.new_filter(filter.clone()).await,
+        FilterKind::Blocks => eth.new_block_filter().await,
+        FilterKind::PendingTransactions => eth.new_pending_transaction_filter().await,
+    }
+}
+
+/// Returns whether the node no longer knows the filter, as geth and erigon report with
+/// "filter not found" and nethermind with "filter with id ... does not exist".
+fn is_filter_not_found(error: &Error) -> bool {
+    match error {
+        Error::Rpc { message, .. } => {
+            let message = message.to_lowercase();
+            message.contains("filter")
+                && (message.contains("not found") || message.contains("does not exist"))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use serde_json::json;
+    use w3b_types::*;
+
+    use crate::{api::eth::Eth, namespace::Namespace, providers::MockProvider};
+
+    #[tokio::test]
+    async fn reinstall_forgotten_filter() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+        let first = Bytes32::from_hex(format!("0x{}", "11".repeat(32))).unwrap();
+        let second = Bytes32::from_hex(format!("0x{}", "22".repeat(32))).unwrap();
+
+        mock.expect("eth_newBlockFilter").returns("0x1");
+        mock.expect("eth_getFilterChanges")
+            .params(vec![json!("0x1")])
+            .returns(json!([first]));
+        mock.expect("eth_getFilterChanges")
+            .params(vec![json!("0x1")])
+            .rpc_error(-32000, "filter not found", None);
+        mock.expect("eth_newBlockFilter").returns("0x2");
+        mock.expect("eth_getFilterChanges")
+            .params(vec![json!("0x2")])
+            .returns(json!([]));
+        mock.expect("eth_getFilterChanges")
+            .params(vec![json!("0x2")])
+            .returns(json!([second]));
+        mock.expect("eth_uninstallFilter")
+            .params(vec![json!("0x2")])
+            .returns(true);
+
+        let mut watcher = eth.watch_blocks(Duration::from_millis(1));
+        assert_eq!(watcher.next().await.unwrap().unwrap(), first);
+        assert_eq!(watcher.next().await.unwrap().unwrap(), second);
+        assert_eq!(watcher.id(), Some(&FilterId::new("0x2")));
+        assert!(watcher.uninstall().await.unwrap());
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn keep_filter_after_failed_poll() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_newFilter").returns("0x1");
+        mock.expect("eth_getFilterChanges")
+            .fails("connection reset");
+        mock.expect("eth_getFilterChanges").returns(json!([]));
+
+        let filter = Filter {
+            blocks: FilterBlocks::Range {
+                from_block: None,
+                to_block: None,
+            },
+            address: None,
+            topics: Vec::new(),
+        };
+
+        let mut watcher = eth.watch_logs(filter, Duration::from_millis(1));
+        assert!(watcher.next().await.unwrap().is_err());
+        assert_eq!(watcher.id(), Some(&FilterId::new("0x1")));
+        assert_eq!(mock.remaining(), 1);
+    }
+}
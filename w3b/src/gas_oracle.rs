@@ -0,0 +1,132 @@
+use std::{convert::TryFrom, future::Future, pin::Pin};
+
+use num_bigint::BigUint;
+use w3b_types::{BlockNumber, FeeHistory, Uint256};
+
+use super::{api::eth::Eth, error::Error, provider::Provider};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fees {
+    pub max_fee_per_gas: Uint256,
+    pub max_priority_fee_per_gas: Uint256,
+}
+
+/// Suggests EIP-1559 fees for new transactions.
+pub trait GasOracle<T: Provider> {
+    type Estimate: Future<Output = Result<Fees, Error>> + Send + 'static;
+
+    fn estimate(&self, eth: &Eth<T>) -> Self::Estimate;
+}
+
+/// Suggests the median of a percentile of the priority fees paid in recent blocks, on top of a
+/// multiple of the next base fee, so that transactions stay valid while base fees rise.
+#[derive(Clone, Debug)]
+pub struct Eip1559Oracle {
+    /// Number of recent blocks to sample.
+    pub block_count: u64,
+    /// Percentile of the priority fees paid within each block.
+    pub reward_percentile: f64,
+    pub base_fee_multiplier: u64,
+}
+
+impl Default for Eip1559Oracle {
+    fn default() -> Self {
+        Self {
+            block_count: 10,
+            reward_percentile: 50.0,
+            base_fee_multiplier: 2,
+        }
+    }
+}
+
+impl Eip1559Oracle {
+    pub fn fees(&self, history: &FeeHistory) -> Result<Fees, Error> {
+        let base_fee = history
+            .base_fee_per_gas
+            .last()
+            .cloned()
+            .map(BigUint::from)
+            .ok_or_else(|| Error::Provider("fee history without base fees".into()))?;
+
+        // Empty blocks report zero rewards, which say nothing about the fees needed.
+        let mut rewards: Vec<_> = history
+            .reward
+            .iter()
+            .filter_map(|rewards| rewards.first().cloned())
+            .map(BigUint::from)
+            .filter(|reward| *reward != BigUint::from(0_u8))
+            .collect();
+        rewards.sort();
+
+        let priority_fee = match rewards.len() {
+            0 => BigUint::from(0_u8),
+            len if len % 2 == 0 => (&rewards[len / 2 - 1] + &rewards[len / 2]) / 2_u8,
+            len => rewards[len / 2].clone(),
+        };
+
+        let max_fee = base_fee * self.base_fee_multiplier + &priority_fee;
+
+        Ok(Fees {
+            max_fee_per_gas: to_uint256(max_fee)?,
+            max_priority_fee_per_gas: to_uint256(priority_fee)?,
+        })
+    }
+}
+
+impl<T: Provider> GasOracle<T> for Eip1559Oracle {
+    type Estimate = Pin<Box<dyn Future<Output = Result<Fees, Error>> + Send>>;
+
+    fn estimate(&self, eth: &Eth<T>) -> Self::Estimate {
+        let history = eth.fee_history(
+            self.block_count,
+            BlockNumber::Latest,
+            &[self.reward_percentile],
+        );
+        let oracle = self.clone();
+
+        Box::pin(async move { oracle.fees(&history.await?) })
+    }
+}
+
+#[inline]
+fn to_uint256(value: BigUint) -> Result<Uint256, Error> {
+    Uint256::try_from(value).map_err(|error| Error::Provider(Box::new(error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::Uint256;
+
+    use super::{Eip1559Oracle, Fees, GasOracle};
+    use crate::{api::eth::Eth, namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn estimate_from_fee_history() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_feeHistory")
+            .params(vec![json!("0x4"), json!("latest"), json!([50.0])])
+            .returns(json!({
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x64", "0x64", "0x64", "0x64", "0xc8"],
+                "gasUsedRatio": [0.5, 0.0, 0.5, 0.5],
+                "reward": [["0x3"], ["0x0"], ["0x1"], ["0xa"]],
+            }));
+
+        let oracle = Eip1559Oracle {
+            block_count: 4,
+            ..Eip1559Oracle::default()
+        };
+
+        assert_eq!(
+            block_on(oracle.estimate(&eth)).unwrap(),
+            Fees {
+                max_fee_per_gas: Uint256::from(403_u64),
+                max_priority_fee_per_gas: Uint256::from(3_u64),
+            },
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Hands out monotonically increasing request ids, shared by every clone of the counter.
+#[derive(Clone)]
+pub struct IdCounter(Arc<AtomicU64>);
+
+impl IdCounter {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicU64::new(1)))
+    }
+
+    #[inline]
+    pub fn next(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl Default for IdCounter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
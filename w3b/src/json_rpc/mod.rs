@@ -1,7 +1,11 @@
+mod id;
+mod notification;
 mod request;
 mod response;
 mod version;
 
+pub use id::*;
+pub use notification::*;
 pub use request::*;
 pub use response::*;
 pub use version::JsonRpcVersion;
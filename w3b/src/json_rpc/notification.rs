@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use super::version::JsonRpcVersion;
+
+#[derive(Deserialize)]
+pub struct Notification {
+    pub jsonrpc: JsonRpcVersion,
+    pub method: String,
+    pub params: NotificationParams,
+}
+
+#[derive(Deserialize)]
+pub struct NotificationParams {
+    pub subscription: String,
+    pub result: serde_json::Value,
+}
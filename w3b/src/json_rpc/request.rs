@@ -2,7 +2,7 @@ use serde::Serialize;
 
 use super::version::JsonRpcVersion;
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Request {
     jsonrpc: JsonRpcVersion,
     id: serde_json::Value,
@@ -20,4 +20,24 @@ impl Request {
             params,
         }
     }
+
+    #[inline]
+    pub fn id(&self) -> &serde_json::Value {
+        &self.id
+    }
+
+    #[inline]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    #[inline]
+    pub fn params(&self) -> &[serde_json::Value] {
+        &self.params
+    }
+
+    #[inline]
+    pub fn set_id(&mut self, id: impl Into<serde_json::Value>) {
+        self.id = id.into();
+    }
 }
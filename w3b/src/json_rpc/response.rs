@@ -1,10 +1,92 @@
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 
 use super::version::JsonRpcVersion;
+use crate::error::Error;
 
 #[derive(Deserialize)]
 pub struct Response {
     pub jsonrpc: JsonRpcVersion,
     pub id: serde_json::Value,
+    #[serde(default)]
     pub result: serde_json::Value,
+    #[serde(default)]
+    pub error: Option<ResponseError>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct ResponseError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+impl Response {
+    #[inline]
+    pub fn into_result<U: DeserializeOwned>(self) -> Result<U, Error> {
+        match self.error {
+            Some(error) => Err(error.into()),
+            None => Ok(serde_json::from_value(self.result)?),
+        }
+    }
+}
+
+/// Returns the error of a response with a null id, which nodes answer requests they could not
+/// parse or identify with.
+pub(crate) fn null_id_error(value: &serde_json::Value) -> Option<Error> {
+    if !value["id"].is_null() || value.get("error").is_none() {
+        return None;
+    }
+
+    let error = serde_json::from_value::<ResponseError>(value["error"].clone())
+        .map_or_else(Error::from, Error::from);
+    Some(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Response;
+    use crate::error::Error;
+
+    #[test]
+    fn into_result() {
+        let response: Response = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": "0x10",
+        }))
+        .unwrap();
+
+        assert_eq!(response.into_result::<String>().unwrap(), "0x10");
+    }
+
+    #[test]
+    fn into_result_with_error() {
+        let response: Response = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {
+                "code": 3,
+                "message": "execution reverted",
+                "data": "0x08c379a0",
+            },
+        }))
+        .unwrap();
+
+        match response.into_result::<String>().unwrap_err() {
+            Error::Rpc {
+                code,
+                message,
+                data,
+            } => {
+                assert_eq!(code, 3);
+                assert_eq!(message, "execution reverted");
+                assert_eq!(data, Some(json!("0x08c379a0")));
+            }
+
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
 }
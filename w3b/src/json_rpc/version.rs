@@ -1,5 +1,6 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[derive(Clone)]
 pub enum JsonRpcVersion {
     V2,
     Other(String),
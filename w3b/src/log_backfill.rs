@@ -0,0 +1,357 @@
+use std::collections::VecDeque;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use w3b_types::{BlockNumber, Filter, FilterBlocks, Log};
+
+use super::{api::eth::Eth, error::Error, provider::Provider};
+
+#[derive(Clone, Debug)]
+pub enum Backfill {
+    Log(Log),
+    /// All the logs up to and including this block were emitted, so that a backfill interrupted
+    /// afterwards resumes from the next block.
+    Checkpoint(u64),
+}
+
+/// Fetches the logs of a block range in chunks, halving them while the node reports them as too
+/// large and doubling them while responses stay small.
+#[derive(Clone, Debug)]
+pub struct LogBackfill {
+    /// Number of blocks of the first chunk.
+    pub initial_chunk: u64,
+    pub max_chunk: u64,
+    /// Chunks grow after responses with fewer logs than this.
+    pub target_logs: usize,
+}
+
+impl Default for LogBackfill {
+    fn default() -> Self {
+        Self {
+            initial_chunk: 1_000,
+            max_chunk: 100_000,
+            target_logs: 1_000,
+        }
+    }
+}
+
+impl LogBackfill {
+    /// Streams the logs matching the filter in block order, followed by a checkpoint after each
+    /// chunk, and ends after the last block of the range or the first error.
+    ///
+    /// Blocks default to `earliest` and `latest` as with `eth_getLogs`, with `latest` resolved
+    /// once when the stream starts. Filters by block hash are fetched in a single request.
+    pub fn stream<T: Provider>(
+        &self,
+        eth: &Eth<T>,
+        filter: impl Into<Filter>,
+    ) -> BoxStream<'static, Result<Backfill, Error>> {
+        let state = State {
+            eth: eth.clone(),
+            filter: filter.into(),
+            policy: self.clone(),
+            chunk: self.initial_chunk.max(1),
+            range: None,
+            buffer: VecDeque::new(),
+            done: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        })
+        .boxed()
+    }
+}
+
+struct State<T: Provider> {
+    eth: Eth<T>,
+    filter: Filter,
+    policy: LogBackfill,
+    chunk: u64,
+    /// Blocks left to fetch, once resolved.
+    range: Option<(u64, u64)>,
+    buffer: VecDeque<Backfill>,
+    done: bool,
+}
+
+impl<T: Provider> State<T> {
+    async fn next(&mut self) -> Option<Result<Backfill, Error>> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Some(Ok(item));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            if let Err(error) = self.fetch().await {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+
+    async fn fetch(&mut self) -> Result<(), Error> {
+        let (from, to) = match self.range {
+            Some(range) => range,
+
+            None => {
+                let range = match &self.filter.blocks {
+                    FilterBlocks::Range {
+                        from_block,
+                        to_block,
+                    } => resolve(&self.eth, *from_block, *to_block).await?,
+
+                    FilterBlocks::Hash { .. } => {
+                        let logs = self.eth.logs(self.filter.clone()).await?;
+                        self.buffer.extend(logs.into_iter().map(Backfill::Log));
+                        self.done = true;
+                        return Ok(());
+                    }
+                };
+
+                self.range = Some(range);
+                range
+            }
+        };
+
+        if from > to {
+            self.done = true;
+            return Ok(());
+        }
+
+        let end = to.min(from.saturating_add(self.chunk - 1));
+        let filter = Filter {
+            blocks: FilterBlocks::Range {
+                from_block: Some(from.into()),
+                to_block: Some(end.into()),
+            },
+            address: self.filter.address.clone(),
+            topics: self.filter.topics.clone(),
+        };
+
+        match self.eth.logs(filter).await {
+            Ok(logs) => {
+                if logs.len() < self.policy.target_logs {
+                    self.chunk = self
+                        .chunk
+                        .saturating_mul(2)
+                        .min(self.policy.max_chunk.max(1));
+                }
+
+                self.buffer.extend(logs.into_iter().map(Backfill::Log));
+                self.buffer.push_back(Backfill::Checkpoint(end));
+
+                if end == to {
+                    self.done = true;
+                } else {
+                    self.range = Some((end + 1, to));
+                }
+
+                Ok(())
+            }
+
+            // A single block cannot be split, so its error is returned as is.
+            Err(error) if end > from && is_too_large(&error) => {
+                let span = end - from + 1;
+                self.chunk = span / 2;
+                Ok(())
+            }
+
+            Err(error) => Err(error),
+        }
+    }
+}
+
+async fn resolve<T: Provider>(
+    eth: &Eth<T>,
+    from_block: Option<BlockNumber>,
+    to_block: Option<BlockNumber>,
+) -> Result<(u64, u64), Error> {
+    let needs_head = to_block.is_none()
+        || [from_block, to_block].iter().any(|block_number| {
+            matches!(
+                block_number,
+                Some(BlockNumber::Latest) | Some(BlockNumber::Pending)
+            )
+        });
+
+    let head = if needs_head {
+        eth.block_number().await?
+    } else {
+        0
+    };
+
+    let number = |block_number, default| match block_number {
+        None => default,
+        Some(BlockNumber::Earliest) => 0,
+        Some(BlockNumber::Number(number)) => number,
+        Some(BlockNumber::Latest) | Some(BlockNumber::Pending) => head,
+    };
+
+    Ok((number(from_block, 0), number(to_block, head)))
+}
+
+/// Returns whether the node refused the range for its size, as with geth's "query returned more
+/// than 10000 results" or the block range limits of hosted nodes.
+///
+/// Only the known size messages match, so that other errors, such as invalid ranges or rate
+/// limits, surface at once instead of after splitting the range down to single blocks.
+fn is_too_large(error: &Error) -> bool {
+    match error {
+        Error::Rpc { code, message, .. } => {
+            let message = message.to_lowercase();
+
+            [
+                "query returned more than",
+                "block range is too large",
+                "response size exceeded",
+            ]
+            .iter()
+            .any(|pattern| message.contains(pattern))
+                || (*code == -32005 && message.contains("limit exceeded"))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, StreamExt};
+    use serde_json::json;
+    use w3b_types::*;
+
+    use super::{Backfill, LogBackfill};
+    use crate::{api::eth::Eth, namespace::Namespace, providers::MockProvider};
+
+    fn range(from_block: u64, to_block: u64) -> serde_json::Value {
+        json!({
+            "fromBlock": format!("0x{:x}", from_block),
+            "toBlock": format!("0x{:x}", to_block),
+            "address": null,
+            "topics": [],
+        })
+    }
+
+    fn log(block_number: u64) -> serde_json::Value {
+        json!({
+            "address": format!("0x{}", "11".repeat(20)),
+            "topics": [],
+            "data": "0x",
+            "blockNumber": format!("0x{:x}", block_number),
+            "removed": false,
+        })
+    }
+
+    fn filter(from_block: Option<BlockNumber>) -> Filter {
+        Filter {
+            blocks: FilterBlocks::Range {
+                from_block,
+                to_block: None,
+            },
+            address: None,
+            topics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn split_and_grow_chunks() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_blockNumber").returns("0x63");
+        mock.expect("eth_getLogs")
+            .params(vec![range(0, 49)])
+            .rpc_error(-32005, "query returned more than 10000 results", None);
+        mock.expect("eth_getLogs")
+            .params(vec![range(0, 24)])
+            .returns(json!([log(3)]));
+        mock.expect("eth_getLogs")
+            .params(vec![range(25, 74)])
+            .returns(json!([]));
+        mock.expect("eth_getLogs")
+            .params(vec![range(75, 99)])
+            .returns(json!([log(80), log(81)]));
+
+        let backfill = LogBackfill {
+            initial_chunk: 50,
+            max_chunk: 100,
+            target_logs: 2,
+        };
+
+        let items: Vec<_> = block_on(backfill.stream(&eth, filter(None)).collect());
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|item| match item.unwrap() {
+                Backfill::Log(log) => format!("log {}", u64::from(log.block_number.unwrap())),
+                Backfill::Checkpoint(block_number) => format!("checkpoint {}", block_number),
+            })
+            .collect();
+
+        assert_eq!(
+            items,
+            vec![
+                "log 3",
+                "checkpoint 24",
+                "checkpoint 74",
+                "log 80",
+                "log 81",
+                "checkpoint 99",
+            ],
+        );
+    }
+
+    #[test]
+    fn end_on_unsplittable_errors() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_blockNumber").returns("0x8");
+        mock.expect("eth_getLogs")
+            .params(vec![range(7, 8)])
+            .rpc_error(-32000, "block range is too large", None);
+        mock.expect("eth_getLogs")
+            .params(vec![range(7, 7)])
+            .rpc_error(-32000, "block range is too large", None);
+
+        let backfill = LogBackfill {
+            initial_chunk: 2,
+            ..LogBackfill::default()
+        };
+
+        let mut stream = backfill.stream(&eth, filter(Some(7_u64.into())));
+        assert!(block_on(stream.next()).unwrap().is_err());
+        assert!(block_on(stream.next()).is_none());
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn end_on_other_errors_without_splitting() {
+        let messages = [
+            (-32000, "invalid block range"),
+            (-32005, "exceeded its compute units per second capacity"),
+            (-32005, "daily request count exceeded, request rate limited"),
+        ];
+
+        for (code, message) in messages.iter().copied() {
+            let mock = MockProvider::new();
+            let eth = Eth::new(mock.clone());
+
+            mock.expect("eth_blockNumber").returns("0x63");
+            mock.expect("eth_getLogs")
+                .params(vec![range(0, 99)])
+                .rpc_error(code, message, None);
+
+            let backfill = LogBackfill {
+                initial_chunk: 100,
+                ..LogBackfill::default()
+            };
+
+            let mut stream = backfill.stream(&eth, filter(None));
+            assert!(block_on(stream.next()).unwrap().is_err(), "{}", message);
+            assert!(block_on(stream.next()).is_none());
+            assert_eq!(mock.remaining(), 0);
+        }
+    }
+}
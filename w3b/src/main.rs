@@ -1,26 +1,44 @@
+use std::env;
+
 use num_bigint::BigUint;
 use w3b_types::{Address, BlockNumber, Bytes32, Filter, FilterBlocks, Topic};
 
 mod api;
+mod batch;
+mod block_stream;
+pub mod blocking;
 mod error;
+mod filter_watcher;
+mod gas_oracle;
 mod json_rpc;
+mod log_backfill;
 mod namespace;
+mod proof;
 mod provider;
 pub mod providers;
+mod subscription;
 mod web3;
 
 pub use api::*;
+pub use batch::*;
+pub use block_stream::*;
 pub use error::*;
+pub use filter_watcher::*;
+pub use gas_oracle::*;
 pub use json_rpc::*;
+pub use log_backfill::*;
 pub use namespace::*;
+pub use proof::*;
 pub use provider::*;
+pub use subscription::*;
 pub use web3::*;
 
 use providers::HttpProvider;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    let provider = HttpProvider::new(env!("JSON_RPC_URI").to_owned());
+    let uri = env::var("JSON_RPC_URI").expect("JSON_RPC_URI must be set");
+    let provider = HttpProvider::new(uri);
     let web3 = Web3::new(provider);
 
     let block_number = web3.eth().block_number().await?;
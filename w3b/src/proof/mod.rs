@@ -0,0 +1,512 @@
+mod rlp;
+
+use std::{error::Error as StdError, fmt};
+
+use tiny_keccak::{Hasher, Keccak};
+use w3b_types::{AccountProof, Address, Bytes, Bytes32, StorageProof, Uint256};
+
+/// Root of a trie without any node, the keccak hash of an empty RLP string.
+const EMPTY_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+/// Code hash of accounts without code, the keccak hash of no bytes.
+const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum ProofError {
+    InvalidRlp,
+    InvalidNode,
+    /// A node does not hash to the reference its parent holds.
+    HashMismatch,
+    /// The proof ends before reaching the key.
+    IncompleteProof,
+    /// The proof goes on after proving the key absent.
+    UnexpectedNode,
+    /// The proof is of another account than the requested one.
+    AddressMismatch,
+    /// The storage proofs are not of the requested slots, in order.
+    StorageKeysMismatch,
+    AccountMismatch,
+    StorageMismatch(Uint256),
+}
+
+impl fmt::Debug for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofError::InvalidRlp => write!(f, "invalid RLP in proof"),
+            ProofError::InvalidNode => write!(f, "invalid trie node in proof"),
+            ProofError::HashMismatch => write!(f, "proof node does not match its hash"),
+            ProofError::IncompleteProof => write!(f, "proof ends before reaching the key"),
+            ProofError::UnexpectedNode => write!(f, "unexpected node after proving the key absent"),
+            ProofError::AddressMismatch => write!(f, "proof is of another account"),
+            ProofError::StorageKeysMismatch => {
+                write!(f, "storage proofs are not of the requested slots")
+            }
+            ProofError::AccountMismatch => write!(f, "account does not match the proof"),
+            ProofError::StorageMismatch(key) => {
+                write!(f, "storage slot {} does not match the proof", key.to_hex())
+            }
+        }
+    }
+}
+
+impl fmt::Display for ProofError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl StdError for ProofError {}
+
+/// Verifies an `eth_getProof` response against the state root of the block it was requested at,
+/// including each of its storage proofs against the proven storage root, and checks that it
+/// proves the requested account and slots.
+pub fn verify_proof(
+    state_root: &Bytes32,
+    address: &Address,
+    storage_keys: &[Uint256],
+    proof: &AccountProof,
+) -> Result<(), ProofError> {
+    if proof.address != *address {
+        return Err(ProofError::AddressMismatch);
+    }
+
+    let keys = proof
+        .storage_proof
+        .iter()
+        .map(|storage_proof| &storage_proof.key);
+    if !keys.eq(storage_keys) {
+        return Err(ProofError::StorageKeysMismatch);
+    }
+
+    let account = verify_path(
+        state_root.as_repr(),
+        proof.address.as_bytes(),
+        &proof.account_proof,
+    )?;
+
+    match account {
+        Some(account) => {
+            let account = rlp::decode(&account)?;
+            let fields = account.list()?;
+
+            if fields.len() != 4 {
+                return Err(ProofError::InvalidNode);
+            }
+
+            let matches = fields[0].string()? == trim(proof.nonce.as_bytes())
+                && fields[1].string()? == trim(proof.balance.as_bytes())
+                && fields[2].string()? == proof.storage_hash.as_bytes()
+                && fields[3].string()? == proof.code_hash.as_bytes();
+
+            if !matches {
+                return Err(ProofError::AccountMismatch);
+            }
+        }
+
+        None => {
+            // Nodes report missing accounts either with empty hashes or with zeros.
+            let is_empty = trim(proof.nonce.as_bytes()).is_empty()
+                && trim(proof.balance.as_bytes()).is_empty()
+                && is_empty_or_zero(&proof.storage_hash, &EMPTY_ROOT)
+                && is_empty_or_zero(&proof.code_hash, &EMPTY_CODE_HASH);
+
+            if !is_empty {
+                return Err(ProofError::AccountMismatch);
+            }
+        }
+    }
+
+    let storage_root = match proof.storage_hash.as_repr() {
+        storage_root if storage_root == &[0; 32] => &EMPTY_ROOT,
+        storage_root => storage_root,
+    };
+
+    for storage_proof in &proof.storage_proof {
+        verify_storage_proof(storage_root, storage_proof)?;
+    }
+
+    Ok(())
+}
+
+fn verify_storage_proof(root: &[u8; 32], proof: &StorageProof) -> Result<(), ProofError> {
+    let value = match verify_path(root, proof.key.as_bytes(), &proof.proof)? {
+        // Slots hold their values as RLP-encoded integers.
+        Some(value) => rlp::decode(&value)?.string()?.to_vec(),
+        None => Vec::new(),
+    };
+
+    if value != trim(proof.value.as_bytes()) {
+        return Err(ProofError::StorageMismatch(proof.key.clone()));
+    }
+
+    Ok(())
+}
+
+/// The reference a trie node holds to a child, either its hash or, for nodes shorter than 32
+/// bytes, the node itself.
+enum Reference {
+    Empty,
+    Hash([u8; 32]),
+    Inline(Vec<u8>),
+}
+
+impl Reference {
+    fn of(item: &rlp::Item) -> Result<Self, ProofError> {
+        match item.string() {
+            Ok([]) => Ok(Reference::Empty),
+
+            Ok(hash) if hash.len() == 32 => {
+                let mut repr = [0; 32];
+                repr.copy_from_slice(hash);
+                Ok(Reference::Hash(repr))
+            }
+
+            Ok(_) => Err(ProofError::InvalidNode),
+            Err(_) => Ok(Reference::Inline(item.raw.to_vec())),
+        }
+    }
+}
+
+/// Walks the proof from the root down to the hash of the key, returning the value stored at the
+/// key, or `None` if the proof shows it absent.
+fn verify_path(
+    root: &[u8; 32],
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    let path: Vec<u8> = keccak256(key)
+        .iter()
+        .flat_map(|byte| vec![byte >> 4, byte & 0xf])
+        .collect();
+
+    let mut path = &path[..];
+    let mut proof = proof.iter();
+    let mut reference = Reference::Hash(*root);
+
+    loop {
+        let node = match reference {
+            Reference::Empty => return finish(proof, None),
+
+            Reference::Hash(hash) => match proof.next() {
+                Some(node) if keccak256(node.as_bytes()) == hash => node.as_bytes().to_vec(),
+                Some(_) => return Err(ProofError::HashMismatch),
+                None if hash == EMPTY_ROOT => return Ok(None),
+                None => return Err(ProofError::IncompleteProof),
+            },
+
+            Reference::Inline(node) => node,
+        };
+
+        let node = rlp::decode(&node)?;
+        let items = node.list()?;
+
+        match items.len() {
+            17 => match path.split_first() {
+                Some((nibble, rest)) => {
+                    path = rest;
+                    reference = Reference::of(&items[*nibble as usize])?;
+                }
+
+                None => {
+                    let value = items[16].string()?;
+                    return finish(proof, Some(value).filter(|value| !value.is_empty()));
+                }
+            },
+
+            2 => {
+                let (is_leaf, node_path) = decode_hex_prefix(items[0].string()?)?;
+
+                if is_leaf {
+                    let value = Some(items[1].string()?).filter(|_| path == &node_path[..]);
+                    return finish(proof, value);
+                }
+
+                if !path.starts_with(&node_path) {
+                    return finish(proof, None);
+                }
+
+                path = &path[node_path.len()..];
+                reference = Reference::of(&items[1])?;
+            }
+
+            _ => return Err(ProofError::InvalidNode),
+        }
+    }
+}
+
+#[inline]
+fn finish<'a>(
+    mut proof: impl Iterator<Item = &'a Bytes>,
+    value: Option<&[u8]>,
+) -> Result<Option<Vec<u8>>, ProofError> {
+    match proof.next() {
+        Some(_) => Err(ProofError::UnexpectedNode),
+        None => Ok(value.map(<[u8]>::to_vec)),
+    }
+}
+
+/// Decodes the path of a leaf or extension node, returning whether it is a leaf and its nibbles.
+fn decode_hex_prefix(bytes: &[u8]) -> Result<(bool, Vec<u8>), ProofError> {
+    let (first, rest) = bytes.split_first().ok_or(ProofError::InvalidNode)?;
+    let flag = first >> 4;
+
+    if flag > 3 {
+        return Err(ProofError::InvalidNode);
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+
+    if flag & 1 == 1 {
+        nibbles.push(first & 0xf);
+    }
+
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0xf);
+    }
+
+    Ok((flag & 2 == 2, nibbles))
+}
+
+#[inline]
+fn is_empty_or_zero(hash: &Bytes32, empty: &[u8; 32]) -> bool {
+    hash.as_repr() == empty || hash.as_repr() == &[0; 32]
+}
+
+/// Strips leading zeros off a big-endian integer, as RLP encodes it.
+#[inline]
+fn trim(bytes: &[u8]) -> &[u8] {
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    &bytes[zeros..]
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0; 32];
+    hasher.update(bytes);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use w3b_types::{AccountProof, Address, Bytes, Bytes32, StorageProof, Uint256, Uint64};
+
+    use super::{keccak256, trim, verify_proof, ProofError, EMPTY_CODE_HASH};
+
+    fn encode_string(bytes: &[u8]) -> Vec<u8> {
+        match bytes {
+            [byte] if *byte < 0x80 => vec![*byte],
+            _ => [prefix(0x80, bytes.len()), bytes.to_vec()].concat(),
+        }
+    }
+
+    fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        [prefix(0xc0, payload.len()), payload].concat()
+    }
+
+    fn prefix(offset: u8, len: usize) -> Vec<u8> {
+        if len < 56 {
+            return vec![offset + len as u8];
+        }
+
+        let len = trim(&(len as u64).to_be_bytes()).to_vec();
+        [vec![offset + 55 + len.len() as u8], len].concat()
+    }
+
+    /// Encodes a leaf node below a branch, holding the rest of its path after the first nibble.
+    fn leaf(key: &[u8], value: Vec<u8>) -> Vec<u8> {
+        let hash = keccak256(key);
+        let path = [vec![0x30 | (hash[0] & 0xf)], hash[1..].to_vec()].concat();
+        encode_list(&[encode_string(&path), encode_string(&value)])
+    }
+
+    /// Encodes a leaf node at the root, holding its whole path.
+    fn root_leaf(key: &[u8], value: Vec<u8>) -> Vec<u8> {
+        let path = [vec![0x20], keccak256(key).to_vec()].concat();
+        encode_list(&[encode_string(&path), encode_string(&value)])
+    }
+
+    fn branch(children: &[(&[u8], &[u8])]) -> Vec<u8> {
+        let mut items = vec![encode_string(&[]); 17];
+
+        for (key, node) in children {
+            items[(keccak256(key)[0] >> 4) as usize] = encode_string(&keccak256(node));
+        }
+
+        encode_list(&items)
+    }
+
+    /// Verifies the proof against the account and slots it claims to prove.
+    fn verify(state_root: &Bytes32, proof: &AccountProof) -> Result<(), ProofError> {
+        let storage_keys: Vec<_> = proof
+            .storage_proof
+            .iter()
+            .map(|storage_proof| storage_proof.key.clone())
+            .collect();
+        verify_proof(state_root, &proof.address, &storage_keys, proof)
+    }
+
+    struct Fixture {
+        state_root: Bytes32,
+        proof: AccountProof,
+        /// An address whose path leads to an empty branch slot.
+        missing: Address,
+    }
+
+    fn fixture() -> Fixture {
+        let slot = Uint256::from(0_u64);
+        let storage_leaf = root_leaf(slot.as_bytes(), encode_string(&[0x2a]));
+        let storage_root = keccak256(&storage_leaf);
+
+        let address = Address::new([0x11; 20]);
+        let nibble = |address: &Address| keccak256(address.as_bytes())[0] >> 4;
+
+        // Find addresses landing in other slots of the root branch than the proven one.
+        let mut others = (0..=255_u8).map(|byte| Address::new([byte; 20]));
+        let other = others
+            .find(|other| nibble(other) != nibble(&address))
+            .unwrap();
+        let missing = others
+            .find(|missing| {
+                nibble(missing) != nibble(&address) && nibble(missing) != nibble(&other)
+            })
+            .unwrap();
+
+        let account = encode_list(&[
+            encode_string(&[0x01]),
+            encode_string(&[0x03, 0xe8]),
+            encode_string(&storage_root),
+            encode_string(&EMPTY_CODE_HASH),
+        ]);
+        let account_leaf = leaf(address.as_bytes(), account.clone());
+        let other_leaf = leaf(other.as_bytes(), account);
+
+        let root = branch(&[
+            (address.as_bytes(), &account_leaf),
+            (other.as_bytes(), &other_leaf),
+        ]);
+
+        let proof = AccountProof {
+            address,
+            account_proof: vec![Bytes::new(root.clone()), Bytes::new(account_leaf)],
+            balance: Uint256::from(1000_u64),
+            code_hash: Bytes32::new(EMPTY_CODE_HASH),
+            nonce: Uint64::from(1_u64),
+            storage_hash: Bytes32::new(storage_root),
+            storage_proof: vec![StorageProof {
+                key: slot,
+                value: Uint256::from(0x2a_u64),
+                proof: vec![Bytes::new(storage_leaf)],
+            }],
+        };
+
+        Fixture {
+            state_root: Bytes32::new(keccak256(&root)),
+            proof,
+            missing,
+        }
+    }
+
+    #[test]
+    fn verify_account_and_storage() {
+        let Fixture {
+            state_root, proof, ..
+        } = fixture();
+
+        assert_eq!(verify(&state_root, &proof), Ok(()));
+    }
+
+    #[test]
+    fn reject_tampered_values() {
+        let Fixture {
+            state_root, proof, ..
+        } = fixture();
+
+        let mut tampered = proof.clone();
+        tampered.balance = Uint256::from(1001_u64);
+        assert_eq!(
+            verify(&state_root, &tampered),
+            Err(ProofError::AccountMismatch),
+        );
+
+        let mut tampered = proof.clone();
+        tampered.storage_proof[0].value = Uint256::from(0_u64);
+        assert_eq!(
+            verify(&state_root, &tampered),
+            Err(ProofError::StorageMismatch(Uint256::from(0_u64))),
+        );
+
+        let mut tampered = proof;
+        tampered.account_proof[1] = Bytes::new(vec![0xc0]);
+        assert_eq!(
+            verify(&state_root, &tampered),
+            Err(ProofError::HashMismatch),
+        );
+    }
+
+    #[test]
+    fn reject_proofs_of_other_accounts_or_slots() {
+        let Fixture {
+            state_root,
+            proof,
+            missing,
+        } = fixture();
+        let slot = Uint256::from(0_u64);
+
+        assert_eq!(
+            verify_proof(&state_root, &missing, &[Uint256::from(0_u64)], &proof),
+            Err(ProofError::AddressMismatch),
+        );
+        assert_eq!(
+            verify_proof(&state_root, &proof.address, &[Uint256::from(1_u64)], &proof),
+            Err(ProofError::StorageKeysMismatch),
+        );
+        assert_eq!(
+            verify_proof(&state_root, &proof.address, &[slot.clone(), slot], &proof),
+            Err(ProofError::StorageKeysMismatch),
+        );
+        assert_eq!(
+            verify_proof(&state_root, &proof.address, &[], &proof),
+            Err(ProofError::StorageKeysMismatch),
+        );
+    }
+
+    #[test]
+    fn verify_missing_account() {
+        let Fixture {
+            state_root,
+            proof,
+            missing,
+        } = fixture();
+
+        let mut absent = AccountProof {
+            address: missing,
+            account_proof: vec![proof.account_proof[0].clone()],
+            balance: Uint256::from(0_u64),
+            code_hash: Bytes32::new([0; 32]),
+            nonce: Uint64::from(0_u64),
+            storage_hash: Bytes32::new([0; 32]),
+            storage_proof: vec![StorageProof {
+                key: Uint256::from(1_u64),
+                value: Uint256::from(0_u64),
+                proof: vec![],
+            }],
+        };
+        assert_eq!(verify(&state_root, &absent), Ok(()));
+
+        absent.balance = Uint256::from(1_u64);
+        assert_eq!(
+            verify(&state_root, &absent),
+            Err(ProofError::AccountMismatch),
+        );
+    }
+}
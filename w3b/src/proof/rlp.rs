@@ -0,0 +1,87 @@
+use super::ProofError;
+
+/// An RLP item borrowed from its encoding.
+pub(super) struct Item<'a> {
+    /// The whole encoding of the item, including its prefix.
+    pub raw: &'a [u8],
+    payload: &'a [u8],
+    is_list: bool,
+}
+
+impl<'a> Item<'a> {
+    pub fn string(&self) -> Result<&'a [u8], ProofError> {
+        if self.is_list {
+            return Err(ProofError::InvalidNode);
+        }
+
+        Ok(self.payload)
+    }
+
+    pub fn list(&self) -> Result<Vec<Item<'a>>, ProofError> {
+        if !self.is_list {
+            return Err(ProofError::InvalidNode);
+        }
+
+        let mut items = Vec::new();
+        let mut rest = self.payload;
+
+        while !rest.is_empty() {
+            let (item, tail) = decode_prefix(rest)?;
+            items.push(item);
+            rest = tail;
+        }
+
+        Ok(items)
+    }
+}
+
+/// Decodes a single item spanning all the given bytes.
+pub(super) fn decode(bytes: &[u8]) -> Result<Item<'_>, ProofError> {
+    match decode_prefix(bytes)? {
+        (item, []) => Ok(item),
+        _ => Err(ProofError::InvalidRlp),
+    }
+}
+
+fn decode_prefix(bytes: &[u8]) -> Result<(Item<'_>, &[u8]), ProofError> {
+    let prefix = *bytes.first().ok_or(ProofError::InvalidRlp)?;
+
+    let (offset, len, is_list) = match prefix {
+        0x00..=0x7f => (0, 1, false),
+        0x80..=0xb7 => (1, (prefix - 0x80) as usize, false),
+        0xb8..=0xbf => long(bytes, (prefix - 0xb7) as usize, false)?,
+        0xc0..=0xf7 => (1, (prefix - 0xc0) as usize, true),
+        0xf8..=0xff => long(bytes, (prefix - 0xf7) as usize, true)?,
+    };
+
+    let end = offset
+        .checked_add(len)
+        .filter(|end| *end <= bytes.len())
+        .ok_or(ProofError::InvalidRlp)?;
+
+    let item = Item {
+        raw: &bytes[..end],
+        payload: &bytes[offset..end],
+        is_list,
+    };
+
+    Ok((item, &bytes[end..]))
+}
+
+/// Reads the length following the prefix of a long string or list.
+fn long(
+    bytes: &[u8],
+    len_of_len: usize,
+    is_list: bool,
+) -> Result<(usize, usize, bool), ProofError> {
+    let len_bytes = bytes
+        .get(1..1 + len_of_len)
+        .filter(|len_bytes| len_of_len <= 8 && len_bytes[0] != 0)
+        .ok_or(ProofError::InvalidRlp)?;
+
+    let len = len_bytes
+        .iter()
+        .fold(0_u64, |len, byte| len << 8 | *byte as u64);
+
+    Ok((1 + len_of_len, len as usize, is_list))
+}
@@ -1,14 +1,32 @@
 use std::future::Future;
 
+use futures::Stream;
+
 use super::{error::Error, json_rpc::Request};
 
-pub trait Provider: Clone {
-    type Response: Future<Output = Result<serde_json::Value, Error>>;
+pub trait Provider: Clone + Send + Sync + 'static {
+    type Response: Future<Output = Result<serde_json::Value, Error>> + Send + 'static;
+    type BatchResponse: Future<Output = Result<Vec<serde_json::Value>, Error>> + Send + 'static;
 
     fn send(&self, request: Request) -> Self::Response;
 
+    /// Sends all requests at once, resolving to their responses in the same order as the requests,
+    /// regardless of the order the node answered in.
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse;
+
     #[inline]
     fn execute(&self, method: &str, params: Vec<serde_json::Value>) -> Self::Response {
         self.send(Request::new(method.to_owned(), params))
     }
 }
+
+pub trait PubSubProvider: Provider {
+    type Notifications: Stream<Item = serde_json::Value> + Send + Unpin + 'static;
+    type SubscribeResponse: Future<Output = Result<(serde_json::Value, Self::Notifications), Error>>
+        + Send
+        + 'static;
+
+    fn subscribe(&self, request: Request) -> Self::SubscribeResponse;
+
+    fn unsubscribe(&self, subscription_id: String) -> Self::Response;
+}
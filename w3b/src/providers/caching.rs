@@ -0,0 +1,647 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs,
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::future;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use w3b_types::Hex;
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+    /// Maximum number of responses kept in memory.
+    pub capacity: usize,
+    /// Number of blocks on top of a block before requests against it are cached.
+    pub confirmations: u64,
+    /// How long the last known head is trusted before fetching it again to check confirmations.
+    pub head_ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            confirmations: 64,
+            head_ttl: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A second tier of storage for cached results, such as [`DiskStore`].
+pub trait CacheStore: Send + Sync + 'static {
+    fn get(&self, key: &str) -> io::Result<Option<serde_json::Value>>;
+
+    fn insert(&self, key: &str, result: &serde_json::Value) -> io::Result<()>;
+}
+
+/// Stores each cached result as a JSON file in a directory, named after the hash of its namespace
+/// and request.
+#[derive(Clone, Debug)]
+pub struct DiskStore {
+    dir: PathBuf,
+    namespace: String,
+}
+
+impl DiskStore {
+    /// Opens a store whose entries are kept apart from the ones of other namespaces in the same
+    /// directory, such as the name of the chain or endpoint, as requests alone do not tell
+    /// which chain they were answered on.
+    pub fn open(dir: impl Into<PathBuf>, namespace: impl Into<String>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir,
+            namespace: namespace.into(),
+        })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let mut name = String::with_capacity(69);
+        let mut hasher = Sha256::new();
+
+        // Length-prefixed, so that no namespace and key pair hashes as another.
+        hasher.update((self.namespace.len() as u64).to_be_bytes());
+        hasher.update(self.namespace.as_bytes());
+        hasher.update(key.as_bytes());
+
+        for byte in hasher.finalize() {
+            write!(name, "{:02x}", byte).unwrap();
+        }
+
+        name.push_str(".json");
+        self.dir.join(name)
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> io::Result<Option<serde_json::Value>> {
+        match fs::read(self.path(key)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn insert(&self, key: &str, result: &serde_json::Value) -> io::Result<()> {
+        // Write to a temporary file first, so that readers never see a partial entry.
+        let path = self.path(key);
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, serde_json::to_vec(result)?)?;
+        fs::rename(temp, path)
+    }
+}
+
+/// When the result of a request stays the same forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cacheability {
+    Never,
+    Always,
+    /// Once the result is not `null`, e.g. for lookups by block hash.
+    NonNull,
+    /// Once the block with the given number is confirmed.
+    AtBlock(u64),
+    /// Once the result is not `null` and the block in its `blockNumber` is confirmed.
+    ResultBlock,
+}
+
+impl Cacheability {
+    fn of(request: &Request) -> Self {
+        let params = request.params();
+
+        let param = |index: usize| match params.get(index) {
+            Some(block) => at_block(block),
+            None => Cacheability::Never,
+        };
+
+        match request.method() {
+            "eth_chainId" | "net_version" => Cacheability::Always,
+
+            "eth_getBlockByHash"
+            | "eth_getBlockTransactionCountByHash"
+            | "eth_getTransactionByBlockHashAndIndex"
+            | "eth_getUncleByBlockHashAndIndex"
+            | "eth_getUncleCountByBlockHash" => Cacheability::NonNull,
+
+            "eth_getTransactionByHash" | "eth_getTransactionReceipt" => Cacheability::ResultBlock,
+
+            "eth_getBlockByNumber"
+            | "eth_getBlockReceipts"
+            | "eth_getBlockTransactionCountByNumber"
+            | "eth_getTransactionByBlockNumberAndIndex"
+            | "eth_getUncleByBlockNumberAndIndex"
+            | "eth_getUncleCountByBlockNumber" => param(0),
+
+            "eth_call" | "eth_getBalance" | "eth_getCode" | "eth_getTransactionCount" => param(1),
+
+            "eth_getProof" | "eth_getStorageAt" => param(2),
+
+            "eth_getLogs" => match params.first() {
+                Some(filter) if filter.get("blockHash").is_some() => Cacheability::Always,
+
+                // Without a `fromBlock`, the range starts at the latest block.
+                Some(filter) if filter.get("fromBlock").is_some() => match filter.get("toBlock") {
+                    Some(to_block) => at_block(to_block),
+                    None => Cacheability::Never,
+                },
+
+                _ => Cacheability::Never,
+            },
+
+            _ => Cacheability::Never,
+        }
+    }
+}
+
+/// Parses a block parameter, either a block number, a tag, or an EIP-1898 object.
+fn at_block(block: &serde_json::Value) -> Cacheability {
+    if block.get("blockHash").is_some() {
+        return Cacheability::Always;
+    }
+
+    let block = block.get("blockNumber").unwrap_or(block);
+
+    match block.as_str() {
+        Some("earliest") => Cacheability::AtBlock(0),
+
+        Some(_) => match number(block) {
+            Some(number) => Cacheability::AtBlock(number),
+            None => Cacheability::Never,
+        },
+
+        None => Cacheability::Never,
+    }
+}
+
+#[inline]
+fn number(value: &serde_json::Value) -> Option<u64> {
+    serde_json::from_value::<Hex<u64>>(value.clone())
+        .ok()
+        .map(Hex::inner)
+}
+
+/// A least recently used map of request keys to results.
+struct Lru {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (serde_json::Value, u64)>,
+    ticks: BTreeMap<u64, String>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            ticks: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<serde_json::Value> {
+        let tick = self.tick + 1;
+        let (result, last_used) = self.entries.get_mut(key)?;
+        let key = self.ticks.remove(last_used).unwrap();
+
+        self.tick = tick;
+        *last_used = tick;
+        let result = result.clone();
+        self.ticks.insert(tick, key);
+
+        Some(result)
+    }
+
+    fn insert(&mut self, key: String, result: serde_json::Value) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (result, self.tick)) {
+            self.ticks.remove(&last_used);
+        } else if self.entries.len() > self.capacity {
+            let (_, evicted) = self.ticks.pop_first().unwrap();
+            self.entries.remove(&evicted);
+        }
+
+        self.ticks.insert(self.tick, key);
+    }
+}
+
+/// Caches responses to requests whose results never change, such as lookups by block hash or
+/// requests against blocks deeper than the confirmation depth.
+#[derive(Clone)]
+pub struct CachingProvider<P: Provider> {
+    provider: P,
+    policy: Arc<CachePolicy>,
+    memory: Arc<Mutex<Lru>>,
+    store: Option<Arc<dyn CacheStore>>,
+    head: Arc<Mutex<Option<(u64, Instant)>>>,
+}
+
+impl<P: Provider> CachingProvider<P> {
+    #[inline]
+    pub fn new(provider: P) -> Self {
+        Self::with_policy(provider, CachePolicy::default())
+    }
+
+    pub fn with_policy(provider: P, policy: CachePolicy) -> Self {
+        Self {
+            provider,
+            memory: Arc::new(Mutex::new(Lru::new(policy.capacity))),
+            policy: Arc::new(policy),
+            store: None,
+            head: Default::default(),
+        }
+    }
+
+    /// Keeps cached results in the given store as well, checking it on memory misses.
+    pub fn with_store(provider: P, policy: CachePolicy, store: impl CacheStore) -> Self {
+        Self {
+            store: Some(Arc::new(store)),
+            ..Self::with_policy(provider, policy)
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        if let Some(result) = self.memory.lock().unwrap().get(key) {
+            return Some(result);
+        }
+
+        // The store is only a cache, so failing to read it is the same as missing it.
+        let result = self.store.as_ref()?.get(key).ok()??;
+        self.memory
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), result.clone());
+        Some(result)
+    }
+
+    fn insert(&self, key: String, result: &serde_json::Value) {
+        if let Some(store) = &self.store {
+            let _ = store.insert(&key, result);
+        }
+
+        self.memory.lock().unwrap().insert(key, result.clone());
+    }
+
+    /// Returns the latest block number, fetching it if the last known one is too old.
+    async fn head(&self) -> Result<u64, Error> {
+        if let Some((head, updated)) = *self.head.lock().unwrap() {
+            if updated.elapsed() < self.policy.head_ttl {
+                return Ok(head);
+            }
+        }
+
+        let response = self.provider.execute("eth_blockNumber", vec![]).await?;
+        let head = number(&response["result"])
+            .ok_or_else(|| Error::Provider("invalid eth_blockNumber response".into()))?;
+        self.observe_head(head);
+        Ok(head)
+    }
+
+    fn observe_head(&self, head: u64) {
+        *self.head.lock().unwrap() = Some((head, Instant::now()));
+    }
+
+    async fn is_confirmed(&self, number: u64) -> bool {
+        match self.head().await {
+            Ok(head) => head.saturating_sub(self.policy.confirmations) >= number,
+            Err(_) => false,
+        }
+    }
+
+    /// Caches the result of a successful response if it can never change.
+    async fn remember(
+        &self,
+        cacheability: Cacheability,
+        key: String,
+        response: &serde_json::Value,
+    ) {
+        if matches!(response.get("error"), Some(error) if !error.is_null()) {
+            return;
+        }
+
+        let result = match response.get("result") {
+            Some(result) => result,
+            None => return,
+        };
+
+        let cacheable = match cacheability {
+            Cacheability::Never => false,
+            Cacheability::Always => true,
+            Cacheability::NonNull => !result.is_null(),
+            Cacheability::AtBlock(number) => self.is_confirmed(number).await,
+
+            Cacheability::ResultBlock => match number(&result["blockNumber"]) {
+                Some(number) => self.is_confirmed(number).await,
+                None => false,
+            },
+        };
+
+        if cacheable {
+            self.insert(key, result);
+        }
+    }
+
+    async fn send_cached(self, request: Request) -> Result<serde_json::Value, Error> {
+        let cacheability = Cacheability::of(&request);
+
+        if cacheability == Cacheability::Never {
+            let is_head = request.method() == "eth_blockNumber";
+            let response = self.provider.send(request).await?;
+
+            if is_head {
+                if let Some(head) = number(&response["result"]) {
+                    self.observe_head(head);
+                }
+            }
+
+            return Ok(response);
+        }
+
+        let key = key(&request);
+
+        if let Some(result) = self.get(&key) {
+            return Ok(envelope(&request, result));
+        }
+
+        let response = self.provider.send(request).await?;
+        self.remember(cacheability, key, &response).await;
+        Ok(response)
+    }
+
+    async fn send_batch_cached(
+        self,
+        requests: Vec<Request>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        let mut responses = Vec::with_capacity(requests.len());
+        let mut misses = Vec::new();
+
+        for request in requests {
+            let cacheability = Cacheability::of(&request);
+            let key = key(&request);
+
+            let hit = match cacheability {
+                Cacheability::Never => None,
+                _ => self.get(&key),
+            };
+
+            match hit {
+                Some(result) => responses.push(Some(envelope(&request, result))),
+
+                None => {
+                    responses.push(None);
+                    misses.push((request, cacheability, key));
+                }
+            }
+        }
+
+        if !misses.is_empty() {
+            let requests = misses.iter().map(|(request, ..)| request.clone()).collect();
+            let mut values = self.provider.send_batch(requests).await?.into_iter();
+
+            let remembered = misses.into_iter().map(|(_, cacheability, key)| {
+                let response = values.next().unwrap_or_default();
+                let provider = self.clone();
+
+                async move {
+                    provider.remember(cacheability, key, &response).await;
+                    response
+                }
+            });
+
+            let mut fetched = future::join_all(remembered).await.into_iter();
+
+            for response in responses.iter_mut().filter(|response| response.is_none()) {
+                *response = fetched.next();
+            }
+        }
+
+        Ok(responses
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+}
+
+impl<P: Provider> Provider for CachingProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        Box::pin(self.clone().send_cached(request))
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        Box::pin(self.clone().send_batch_cached(requests))
+    }
+}
+
+#[inline]
+fn key(request: &Request) -> String {
+    json!([request.method(), request.params()]).to_string()
+}
+
+#[inline]
+fn envelope(request: &Request, result: serde_json::Value) -> serde_json::Value {
+    json!({ "jsonrpc": "2.0", "id": request.id(), "result": result })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    use super::{CachePolicy, CacheStore, Cacheability, CachingProvider, DiskStore, Lru};
+    use crate::{json_rpc::Request, provider::Provider, providers::MockProvider};
+
+    fn request(method: &str, params: Vec<serde_json::Value>) -> Request {
+        Request::new(method.to_owned(), params)
+    }
+
+    #[test]
+    fn classify_requests() {
+        let cases = vec![
+            (request("eth_chainId", vec![]), Cacheability::Always),
+            (request("eth_blockNumber", vec![]), Cacheability::Never),
+            (
+                request("eth_getBlockByHash", vec![json!("0x01"), json!(false)]),
+                Cacheability::NonNull,
+            ),
+            (
+                request("eth_getBlockByNumber", vec![json!("0x10"), json!(false)]),
+                Cacheability::AtBlock(16),
+            ),
+            (
+                request("eth_getBlockByNumber", vec![json!("latest"), json!(false)]),
+                Cacheability::Never,
+            ),
+            (
+                request("eth_getBalance", vec![json!("0x00"), json!("pending")]),
+                Cacheability::Never,
+            ),
+            (
+                request("eth_getBalance", vec![json!("0x00")]),
+                Cacheability::Never,
+            ),
+            (
+                request(
+                    "eth_getStorageAt",
+                    vec![json!("0x00"), json!("0x0"), json!("0x2")],
+                ),
+                Cacheability::AtBlock(2),
+            ),
+            (
+                request("eth_call", vec![json!({}), json!({ "blockHash": "0x01" })]),
+                Cacheability::Always,
+            ),
+            (
+                request(
+                    "eth_getLogs",
+                    vec![json!({ "fromBlock": "0x1", "toBlock": "0x5" })],
+                ),
+                Cacheability::AtBlock(5),
+            ),
+            (
+                request("eth_getLogs", vec![json!({ "fromBlock": "0x1" })]),
+                Cacheability::Never,
+            ),
+            (
+                request("eth_getTransactionReceipt", vec![json!("0x01")]),
+                Cacheability::ResultBlock,
+            ),
+        ];
+
+        for (request, cacheability) in cases {
+            assert_eq!(
+                Cacheability::of(&request),
+                cacheability,
+                "{}",
+                request.method()
+            );
+        }
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut lru = Lru::new(2);
+        lru.insert("a".to_owned(), json!(1));
+        lru.insert("b".to_owned(), json!(2));
+        assert_eq!(lru.get("a"), Some(json!(1)));
+
+        lru.insert("c".to_owned(), json!(3));
+        assert_eq!(lru.get("b"), None);
+        assert_eq!(lru.get("a"), Some(json!(1)));
+        assert_eq!(lru.get("c"), Some(json!(3)));
+    }
+
+    #[test]
+    fn cache_confirmed_blocks_only() {
+        let mock = MockProvider::new();
+        let policy = CachePolicy {
+            confirmations: 11,
+            ..CachePolicy::default()
+        };
+        let provider = CachingProvider::with_policy(mock.clone(), policy);
+
+        let old = || request("eth_getBlockByNumber", vec![json!("0x5"), json!(false)]);
+        let new = || request("eth_getBlockByNumber", vec![json!("0x14"), json!(false)]);
+
+        mock.expect("eth_getBlockByNumber")
+            .returns(json!({ "number": "0x5" }));
+        mock.expect("eth_blockNumber").returns("0x1e");
+        mock.expect("eth_getBlockByNumber")
+            .returns(json!({ "number": "0x14" }));
+        mock.expect("eth_getBlockByNumber")
+            .returns(json!({ "number": "0x14" }));
+
+        for _ in 0..3 {
+            let response = block_on(provider.send(old())).unwrap();
+            assert_eq!(response["result"]["number"], "0x5");
+        }
+
+        for _ in 0..2 {
+            let response = block_on(provider.send(new())).unwrap();
+            assert_eq!(response["result"]["number"], "0x14");
+        }
+
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn serve_batch_hits_from_cache() {
+        let mock = MockProvider::new();
+        let provider = CachingProvider::new(mock.clone());
+
+        mock.expect("eth_chainId").returns("0x1");
+        block_on(provider.send(request("eth_chainId", vec![]))).unwrap();
+
+        mock.expect("eth_gasPrice").returns("0x2");
+        mock.expect("eth_getBlockByHash").returns(json!(null));
+
+        let responses = block_on(provider.send_batch(vec![
+            request("eth_gasPrice", vec![]),
+            request("eth_chainId", vec![]),
+            request("eth_getBlockByHash", vec![json!("0x01"), json!(false)]),
+        ]))
+        .unwrap();
+
+        assert_eq!(responses[0]["result"], "0x2");
+        assert_eq!(responses[1]["result"], "0x1");
+        assert_eq!(responses[2]["result"], json!(null));
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn persist_to_disk() {
+        let dir = env::temp_dir().join(format!("w3b-cache-{}", std::process::id()));
+        let store = DiskStore::open(&dir, "mainnet").unwrap();
+
+        let mock = MockProvider::new();
+        mock.expect("eth_chainId").returns("0x1");
+
+        let provider = CachingProvider::with_store(mock, CachePolicy::default(), store.clone());
+        block_on(provider.send(request("eth_chainId", vec![]))).unwrap();
+
+        // A fresh provider over the same directory answers without the node.
+        let provider =
+            CachingProvider::with_store(MockProvider::new(), CachePolicy::default(), store.clone());
+        let response = block_on(provider.send(request("eth_chainId", vec![]))).unwrap();
+        assert_eq!(response["result"], "0x1");
+        assert_eq!(store.get("unknown").unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn separate_namespaces() {
+        let dir = env::temp_dir().join(format!("w3b-cache-namespaces-{}", std::process::id()));
+        let mainnet = DiskStore::open(&dir, "mainnet").unwrap();
+        let sepolia = DiskStore::open(&dir, "sepolia").unwrap();
+        let key = r#"["eth_chainId",[]]"#;
+
+        mainnet.insert(key, &json!("0x1")).unwrap();
+        assert_eq!(sepolia.get(key).unwrap(), None);
+
+        sepolia.insert(key, &json!("0xaa36a7")).unwrap();
+        assert_eq!(mainnet.get(key).unwrap(), Some(json!("0x1")));
+        assert_eq!(sepolia.get(key).unwrap(), Some(json!("0xaa36a7")));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,399 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+};
+
+use futures::{
+    channel::{mpsc, oneshot},
+    future, SinkExt,
+};
+
+use crate::{
+    error::Error,
+    json_rpc::{null_id_error, IdCounter, Notification, NotificationParams, Request},
+};
+
+pub type Notifications = mpsc::UnboundedReceiver<serde_json::Value>;
+
+type ResponseSender = oneshot::Sender<Result<serde_json::Value, Error>>;
+
+pub enum Command {
+    Request {
+        id: u64,
+        payload: String,
+        response: ResponseSender,
+        notifications: Option<mpsc::UnboundedSender<serde_json::Value>>,
+    },
+    Batch {
+        payload: String,
+        responses: Vec<(u64, ResponseSender)>,
+    },
+    Unsubscribe {
+        subscription_id: String,
+    },
+}
+
+/// The sending half of a socket transport, shared by every clone of a provider.
+#[derive(Clone)]
+pub struct Handle {
+    commands: mpsc::UnboundedSender<Command>,
+    ids: IdCounter,
+}
+
+impl Handle {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<Command>) {
+        let (commands, receiver) = mpsc::unbounded();
+        let ids = IdCounter::new();
+        (Self { commands, ids }, receiver)
+    }
+
+    pub fn request(
+        &self,
+        mut request: Request,
+        notifications: Option<mpsc::UnboundedSender<serde_json::Value>>,
+    ) -> impl Future<Output = Result<serde_json::Value, Error>> {
+        let id = self.ids.next();
+        request.set_id(id);
+
+        let (sender, receiver) = oneshot::channel();
+
+        let command = serde_json::to_string(&request).map(|payload| Command::Request {
+            id,
+            payload,
+            response: sender,
+            notifications,
+        });
+
+        let mut commands = self.commands.clone();
+
+        async move {
+            commands.send(command?).await?;
+            receiver.await?
+        }
+    }
+
+    pub fn batch(
+        &self,
+        mut requests: Vec<Request>,
+    ) -> impl Future<Output = Result<Vec<serde_json::Value>, Error>> {
+        let (responses, receivers): (Vec<_>, Vec<_>) = requests
+            .iter_mut()
+            .map(|request| {
+                let id = self.ids.next();
+                request.set_id(id);
+
+                let (sender, receiver) = oneshot::channel();
+                ((id, sender), receiver)
+            })
+            .unzip();
+
+        let command =
+            serde_json::to_string(&requests).map(|payload| Command::Batch { payload, responses });
+
+        let mut commands = self.commands.clone();
+
+        async move {
+            commands.send(command?).await?;
+            future::try_join_all(
+                receivers
+                    .into_iter()
+                    .map(|receiver| async { receiver.await? }),
+            )
+            .await
+        }
+    }
+
+    pub fn subscribe(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<(serde_json::Value, Notifications), Error>> {
+        let (sender, receiver) = mpsc::unbounded();
+        let response = self.request(request, Some(sender));
+        async move { Ok((response.await?, receiver)) }
+    }
+
+    /// Stops forwarding the notifications of a subscription.
+    pub fn forget(&self, subscription_id: String) {
+        let _ = self
+            .commands
+            .unbounded_send(Command::Unsubscribe { subscription_id });
+    }
+}
+
+struct Pending {
+    response: ResponseSender,
+    notifications: Option<mpsc::UnboundedSender<serde_json::Value>>,
+}
+
+/// Matches messages read from a socket against in-flight requests and active subscriptions.
+///
+/// Errors the node cannot tie to a request, answered with a null id, fail the oldest request or
+/// batch still pending, as nodes answer in order the requests they could not parse.
+///
+/// Dropping the dispatcher drops every pending sender, which fails the corresponding requests and
+/// ends the corresponding subscription streams.
+#[derive(Default)]
+pub struct Dispatcher {
+    pending: HashMap<u64, Pending>,
+    /// Ids of the requests and batches in the order they were sent.
+    sent: VecDeque<Vec<u64>>,
+    subscriptions: HashMap<String, mpsc::UnboundedSender<serde_json::Value>>,
+}
+
+impl Dispatcher {
+    /// Registers a command, returning the payload that must be written to the socket, if any.
+    pub fn register(&mut self, command: Command) -> Option<String> {
+        match command {
+            Command::Request {
+                id,
+                payload,
+                response,
+                notifications,
+            } => {
+                self.pending.insert(
+                    id,
+                    Pending {
+                        response,
+                        notifications,
+                    },
+                );
+                self.sent.push_back(vec![id]);
+
+                Some(payload)
+            }
+
+            Command::Batch { payload, responses } => {
+                let mut ids = Vec::with_capacity(responses.len());
+
+                for (id, response) in responses {
+                    let pending = Pending {
+                        response,
+                        notifications: None,
+                    };
+
+                    self.pending.insert(id, pending);
+                    ids.push(id);
+                }
+
+                self.sent.push_back(ids);
+
+                Some(payload)
+            }
+
+            Command::Unsubscribe { subscription_id } => {
+                self.subscriptions.remove(&subscription_id);
+                None
+            }
+        }
+    }
+
+    pub fn dispatch(&mut self, message: &[u8]) -> Result<(), serde_json::Error> {
+        let value = serde_json::from_slice(message)?;
+        self.dispatch_value(value);
+        Ok(())
+    }
+
+    /// Dispatches every complete message at the start of a stream buffer, returning the number of
+    /// bytes consumed. Trailing bytes are the start of a message that has yet to be fully read.
+    pub fn dispatch_stream(&mut self, buffer: &[u8]) -> usize {
+        let mut values = serde_json::Deserializer::from_slice(buffer).into_iter();
+        let mut consumed = 0;
+
+        loop {
+            match values.next() {
+                Some(Ok(value)) => {
+                    self.dispatch_value(value);
+                    consumed = values.byte_offset();
+                }
+
+                Some(Err(error)) if error.is_eof() => return consumed,
+
+                // Nothing after malformed input can be framed reliably, so drop it all.
+                Some(Err(_)) | None => return buffer.len(),
+            }
+        }
+    }
+
+    fn dispatch_value(&mut self, value: serde_json::Value) {
+        match value {
+            serde_json::Value::Array(values) => self.dispatch_batch(values),
+
+            value => match value.get("id").and_then(serde_json::Value::as_u64) {
+                Some(id) if self.pending.contains_key(&id) => self.resolve(id, value),
+
+                _ if null_id_error(&value).is_some() => {
+                    self.fail_oldest(|_| null_id_error(&value).unwrap())
+                }
+
+                // A response to nothing that was sent can only be meant for a pending request.
+                Some(_) => self.fail_oldest(|id| Error::IdMismatch {
+                    expected: id.into(),
+                    actual: value["id"].clone(),
+                }),
+
+                None => {
+                    if let Ok(notification) = serde_json::from_value(value) {
+                        self.notify(notification);
+                    }
+                }
+            },
+        }
+
+        self.prune();
+    }
+
+    /// Resolves the responses of a batch, failing the requests of the batch left unanswered
+    /// with the error the node answered instead, or as an id mismatch as with HTTP.
+    fn dispatch_batch(&mut self, values: Vec<serde_json::Value>) {
+        let mut answered = None;
+        let mut strays = Vec::new();
+
+        for value in values {
+            match value.get("id").and_then(serde_json::Value::as_u64) {
+                Some(id) if self.pending.contains_key(&id) => {
+                    answered.get_or_insert(id);
+                    self.resolve(id, value);
+                }
+
+                _ => strays.push(value),
+            }
+        }
+
+        self.prune();
+
+        let index = match answered {
+            Some(answered) => self.sent.iter().position(|ids| ids.contains(&answered)),
+            None if !strays.is_empty() => Some(0),
+            None => None,
+        };
+
+        let ids = match index.and_then(|index| self.sent.remove(index)) {
+            Some(ids) => ids,
+            None => return,
+        };
+
+        let null_id = strays.iter().find(|stray| null_id_error(stray).is_some());
+        let mut strays = strays.iter();
+        let missing = ids.into_iter().filter(|id| self.pending.contains_key(id));
+
+        for id in missing.collect::<Vec<_>>() {
+            let error = match null_id {
+                Some(null_id) => null_id_error(null_id).unwrap(),
+
+                None => Error::IdMismatch {
+                    expected: id.into(),
+                    actual: strays
+                        .next()
+                        .map_or(serde_json::Value::Null, |stray| stray["id"].clone()),
+                },
+            };
+
+            self.fail(id, error);
+        }
+    }
+
+    fn resolve(&mut self, id: u64, value: serde_json::Value) {
+        if let Some(pending) = self.pending.remove(&id) {
+            // Registering here, before the next message is read, guarantees that no notification
+            // for a fresh subscription is ever missed.
+            if let Some(notifications) = pending.notifications {
+                if let Some(subscription_id) = value.get("result").and_then(|id| id.as_str()) {
+                    self.subscriptions
+                        .insert(subscription_id.to_owned(), notifications);
+                }
+            }
+
+            let _ = pending.response.send(Ok(value));
+        }
+    }
+
+    fn fail(&mut self, id: u64, error: Error) {
+        if let Some(pending) = self.pending.remove(&id) {
+            let _ = pending.response.send(Err(error));
+        }
+    }
+
+    /// Fails every pending request of the oldest request or batch still pending.
+    fn fail_oldest(&mut self, error: impl Fn(u64) -> Error) {
+        self.prune();
+
+        if let Some(ids) = self.sent.pop_front() {
+            for id in ids {
+                self.fail(id, error(id));
+            }
+        }
+    }
+
+    /// Forgets the oldest requests and batches once they have been answered.
+    fn prune(&mut self) {
+        while let Some(ids) = self.sent.front() {
+            if ids.iter().any(|id| self.pending.contains_key(id)) {
+                break;
+            }
+
+            self.sent.pop_front();
+        }
+    }
+
+    fn notify(&mut self, notification: Notification) {
+        let NotificationParams {
+            subscription,
+            result,
+        } = notification.params;
+
+        if let Some(notifications) = self.subscriptions.get(&subscription) {
+            if notifications.unbounded_send(result).is_err() {
+                self.subscriptions.remove(&subscription);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::oneshot;
+    use serde_json::json;
+
+    use super::{Command, Dispatcher};
+    use crate::error::Error;
+
+    #[test]
+    fn fail_unanswered_batch_requests() {
+        let mut dispatcher = Dispatcher::default();
+        let (responses, mut receivers): (Vec<_>, Vec<_>) = (1..=3)
+            .map(|id| {
+                let (sender, receiver) = oneshot::channel();
+                ((id, sender), receiver)
+            })
+            .unzip();
+
+        let payload = String::new();
+        dispatcher.register(Command::Batch { payload, responses });
+        dispatcher.dispatch_value(json!([
+            { "jsonrpc": "2.0", "id": 2, "result": "0x2" },
+            { "jsonrpc": "2.0", "id": 9, "result": "0x9" },
+        ]));
+
+        match receivers[0].try_recv() {
+            Ok(Some(Err(Error::IdMismatch { expected, actual }))) => {
+                assert_eq!((expected, actual), (json!(1), json!(9)));
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+
+        assert_eq!(
+            receivers[1].try_recv().unwrap().unwrap().unwrap()["result"],
+            "0x2"
+        );
+
+        match receivers[2].try_recv() {
+            Ok(Some(Err(Error::IdMismatch { expected, actual }))) => {
+                assert_eq!((expected, actual), (json!(3), json!(null)));
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+
+        assert!(dispatcher.pending.is_empty());
+        assert!(dispatcher.sent.is_empty());
+    }
+}
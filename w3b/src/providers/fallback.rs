@@ -0,0 +1,274 @@
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    slice,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+#[derive(Clone, Copy, Default)]
+struct Health {
+    failures: u32,
+    last_failure: Option<Instant>,
+}
+
+/// Tries its providers in priority order, moving on to the next one whenever a provider fails,
+/// or answers with one of the JSON-RPC error codes of [`rpc_codes`](Self::rpc_codes).
+///
+/// A provider failing `max_failures` times in a row is demoted behind the healthy ones until
+/// `cooldown` has elapsed since its last failure, or until it succeeds again.
+#[derive(Clone)]
+pub struct FallbackProvider<P: Provider> {
+    providers: Arc<Vec<P>>,
+    health: Arc<Mutex<Vec<Health>>>,
+    max_failures: u32,
+    cooldown: Duration,
+    rpc_codes: Arc<HashSet<i64>>,
+}
+
+impl<P: Provider> FallbackProvider<P> {
+    #[inline]
+    pub fn new(providers: Vec<P>) -> Self {
+        Self::with_demotion(providers, 3, Duration::from_secs(30))
+    }
+
+    pub fn with_demotion(providers: Vec<P>, max_failures: u32, cooldown: Duration) -> Self {
+        let health = vec![Health::default(); providers.len()];
+
+        Self {
+            providers: Arc::new(providers),
+            health: Arc::new(Mutex::new(health)),
+            max_failures,
+            cooldown,
+            // -32005 is the "limit exceeded" code from EIP-1474, and -32603 an internal error.
+            rpc_codes: Arc::new([-32005, -32603].iter().copied().collect()),
+        }
+    }
+
+    /// Sets the JSON-RPC error codes to move on to the next provider on.
+    pub fn rpc_codes(mut self, codes: impl IntoIterator<Item = i64>) -> Self {
+        self.rpc_codes = Arc::new(codes.into_iter().collect());
+        self
+    }
+
+    /// Returns the indices of the providers in the order they should be tried.
+    fn order(&self) -> Vec<usize> {
+        let health = self.health.lock().unwrap();
+        let mut order: Vec<_> = (0..self.providers.len()).collect();
+
+        // The sort is stable, so providers of equal health keep their priority.
+        order.sort_by_key(|index| {
+            let Health {
+                failures,
+                last_failure,
+            } = health[*index];
+
+            failures >= self.max_failures
+                && matches!(last_failure, Some(at) if at.elapsed() < self.cooldown)
+        });
+
+        order
+    }
+
+    fn record(&self, index: usize, success: bool) {
+        let health = &mut self.health.lock().unwrap()[index];
+
+        if success {
+            *health = Health::default();
+        } else {
+            health.failures += 1;
+            health.last_failure = Some(Instant::now());
+        }
+    }
+
+    /// Returns whether any of the responses carries one of the error codes to fall back on.
+    fn is_failed_response(&self, responses: &[serde_json::Value]) -> bool {
+        responses
+            .iter()
+            .filter_map(|response| response["error"]["code"].as_i64())
+            .any(|code| self.rpc_codes.contains(&code))
+    }
+
+    /// Calls the providers in order until one succeeds, returning the last result otherwise, so
+    /// that the last node's error response is kept when every node answers with one.
+    async fn fallback<F, R, U, V>(self, call: F, responses: V) -> Result<U, Error>
+    where
+        F: Fn(&P) -> R,
+        R: Future<Output = Result<U, Error>>,
+        V: Fn(&U) -> &[serde_json::Value],
+    {
+        let mut last_result = None;
+
+        for index in self.order() {
+            let result = call(&self.providers[index]).await;
+            let success = match &result {
+                Ok(value) => !self.is_failed_response(responses(value)),
+                Err(_) => false,
+            };
+
+            self.record(index, success);
+
+            if success {
+                return result;
+            }
+
+            last_result = Some(result);
+        }
+
+        last_result.unwrap_or_else(|| Err(Error::Provider("no providers to fall back on".into())))
+    }
+}
+
+impl<P: Provider> Provider for FallbackProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        Box::pin(self.clone().fallback(
+            move |provider| provider.send(request.clone()),
+            slice::from_ref,
+        ))
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        Box::pin(self.clone().fallback(
+            move |provider| provider.send_batch(requests.clone()),
+            Vec::as_slice,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::{
+        executor::block_on,
+        future::{self, Ready},
+    };
+    use serde_json::json;
+
+    use super::FallbackProvider;
+    use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+    #[derive(Clone)]
+    struct Node {
+        name: &'static str,
+        up: bool,
+        /// JSON-RPC error code to answer with while up.
+        error_code: Option<i64>,
+        calls: Arc<AtomicU32>,
+    }
+
+    impl Node {
+        fn new(name: &'static str, up: bool) -> Self {
+            let calls = Default::default();
+            Self {
+                name,
+                up,
+                error_code: None,
+                calls,
+            }
+        }
+
+        fn answering(name: &'static str, code: i64) -> Self {
+            Self {
+                error_code: Some(code),
+                ..Self::new(name, true)
+            }
+        }
+    }
+
+    impl Provider for Node {
+        type Response = Ready<Result<serde_json::Value, Error>>;
+        type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+        fn send(&self, _: Request) -> Self::Response {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+
+            future::ready(match (self.up, self.error_code) {
+                (true, None) => Ok(json!({ "jsonrpc": "2.0", "id": 1, "result": self.name })),
+                (true, Some(code)) => Ok(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": { "code": code, "message": self.name },
+                })),
+                (false, _) => Err(Error::Provider("connection refused".into())),
+            })
+        }
+
+        fn send_batch(&self, _: Vec<Request>) -> Self::BatchResponse {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn fall_back_in_priority_order() {
+        let nodes = vec![
+            Node::new("a", false),
+            Node::new("b", true),
+            Node::new("c", true),
+        ];
+        let provider = FallbackProvider::new(nodes.clone());
+
+        let value = block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+        assert_eq!(value["result"], "b");
+
+        let calls: Vec<_> = nodes
+            .iter()
+            .map(|node| node.calls.load(Ordering::Relaxed))
+            .collect();
+        assert_eq!(calls, vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn fall_back_on_retryable_rpc_errors() {
+        let nodes = vec![
+            Node::answering("a", -32005),
+            Node::answering("b", 3),
+            Node::new("c", true),
+        ];
+        let provider = FallbackProvider::new(nodes.clone());
+
+        // Reverts are answers of their own, not failures of the node.
+        let value = block_on(provider.execute("eth_call", vec![])).unwrap();
+        assert_eq!(value["error"]["message"], "b");
+
+        let provider = FallbackProvider::new(vec![
+            Node::answering("a", -32603),
+            Node::answering("b", -32005),
+        ]);
+        let value = block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+        assert_eq!(value["error"]["code"], -32005);
+    }
+
+    #[test]
+    fn demote_unhealthy_providers() {
+        let nodes = vec![Node::new("a", false), Node::new("b", true)];
+        let provider = FallbackProvider::with_demotion(nodes.clone(), 2, Duration::from_secs(60));
+
+        for _ in 0..5 {
+            block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+        }
+
+        assert_eq!(nodes[0].calls.load(Ordering::Relaxed), 2);
+        assert_eq!(nodes[1].calls.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn fail_when_every_provider_fails() {
+        let provider = FallbackProvider::new(vec![Node::new("a", false), Node::new("b", false)]);
+        let error = block_on(provider.execute("eth_blockNumber", vec![])).unwrap_err();
+        assert_eq!(error.to_string(), "\"connection refused\"");
+    }
+}
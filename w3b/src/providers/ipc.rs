@@ -0,0 +1,254 @@
+use std::{future::Future, path::Path, pin::Pin};
+
+use futures::{channel::mpsc, StreamExt};
+use tokio::{
+    io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::UnixStream,
+};
+
+use super::dispatch::{Command, Dispatcher, Handle, Notifications};
+use crate::{
+    error::Error,
+    json_rpc::Request,
+    provider::{Provider, PubSubProvider},
+};
+
+#[derive(Clone)]
+pub struct IpcProvider {
+    handle: Handle,
+}
+
+impl IpcProvider {
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let stream = UnixStream::connect(path).await?;
+        Ok(Self::with_stream(stream))
+    }
+
+    pub fn with_stream<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (handle, commands) = Handle::new();
+        tokio::spawn(run(stream, commands));
+        Self { handle }
+    }
+}
+
+impl Provider for IpcProvider {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    #[inline]
+    fn send(&self, request: Request) -> Self::Response {
+        Box::pin(self.handle.request(request, None))
+    }
+
+    #[inline]
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        Box::pin(self.handle.batch(requests))
+    }
+}
+
+impl PubSubProvider for IpcProvider {
+    type Notifications = Notifications;
+    type SubscribeResponse = Pin<
+        Box<dyn Future<Output = Result<(serde_json::Value, Self::Notifications), Error>> + Send>,
+    >;
+
+    #[inline]
+    fn subscribe(&self, request: Request) -> Self::SubscribeResponse {
+        Box::pin(self.handle.subscribe(request))
+    }
+
+    fn unsubscribe(&self, subscription_id: String) -> Self::Response {
+        self.handle.forget(subscription_id.clone());
+        self.execute("eth_unsubscribe", vec![subscription_id.into()])
+    }
+}
+
+async fn run<S>(stream: S, mut commands: mpsc::UnboundedReceiver<Command>)
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (mut reader, mut writer) = io::split(stream);
+    let mut dispatcher = Dispatcher::default();
+
+    // Messages are not delimited, so reads accumulate until they hold complete JSON values.
+    let mut buffer = Vec::new();
+    let mut chunk = vec![0; 8192];
+
+    loop {
+        tokio::select! {
+            command = commands.next() => match command {
+                Some(command) => {
+                    if let Some(payload) = dispatcher.register(command) {
+                        if writer.write_all(payload.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+
+                // Every provider handle has been dropped.
+                None => break,
+            },
+
+            read = reader.read(&mut chunk) => match read {
+                Ok(len) if len > 0 => {
+                    buffer.extend_from_slice(&chunk[..len]);
+                    let consumed = dispatcher.dispatch_stream(&buffer);
+                    buffer.drain(..consumed);
+                }
+
+                _ => break,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, process,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use futures::StreamExt;
+    use serde_json::json;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixListener,
+        time::delay_for,
+    };
+
+    use super::IpcProvider;
+    use crate::{
+        api::eth::Eth, error::Error, json_rpc::Request, namespace::Namespace, provider::Provider,
+    };
+
+    /// Serves requests read one per chunk, writing each reply split in two chunks so that the
+    /// provider has to frame messages across reads.
+    async fn serve() -> IpcProvider {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = env::temp_dir().join(format!(
+            "w3b-{}-{}.ipc",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed),
+        ));
+
+        let _ = std::fs::remove_file(&path);
+        let mut listener = UnixListener::bind(&path).unwrap();
+        let provider = IpcProvider::connect(path.clone());
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0; 8192];
+
+            loop {
+                let len = stream.read(&mut buffer).await.unwrap();
+
+                if len == 0 {
+                    break;
+                }
+
+                let request: serde_json::Value = serde_json::from_slice(&buffer[..len]).unwrap();
+                let id = request["id"].clone();
+
+                let reply = match request["method"].as_str().unwrap_or("batch") {
+                    // Nodes rejecting a batch or a request they cannot parse answer a null id.
+                    "batch" => json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": { "code": -32005, "message": "batch limit exceeded" },
+                    })
+                    .to_string(),
+
+                    "eth_chainId" => json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": { "code": -32600, "message": "invalid request" },
+                    })
+                    .to_string(),
+
+                    "eth_blockNumber" => {
+                        json!({ "jsonrpc": "2.0", "id": id, "result": "0x10" }).to_string()
+                    }
+
+                    "eth_subscribe" => format!(
+                        "{}{}",
+                        json!({ "jsonrpc": "2.0", "id": id, "result": "0xbeef" }),
+                        json!({
+                            "jsonrpc": "2.0",
+                            "method": "eth_subscription",
+                            "params": { "subscription": "0xbeef", "result": format!("0x{}", "3".repeat(64)) },
+                        }),
+                    ),
+
+                    "eth_unsubscribe" => {
+                        json!({ "jsonrpc": "2.0", "id": id, "result": true }).to_string()
+                    }
+
+                    _ => unreachable!(),
+                };
+
+                let (head, tail) = reply.as_bytes().split_at(reply.len() / 2);
+                stream.write_all(head).await.unwrap();
+                stream.flush().await.unwrap();
+                delay_for(Duration::from_millis(1)).await;
+                stream.write_all(tail).await.unwrap();
+            }
+
+            let _ = std::fs::remove_file(&path);
+        });
+
+        provider.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn frame_split_responses() {
+        let provider = serve().await;
+
+        for _ in 0..3 {
+            let value = provider.execute("eth_blockNumber", vec![]).await.unwrap();
+            assert_eq!(value["result"], "0x10");
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribe_new_pending_transactions() {
+        let eth = Eth::new(serve().await);
+
+        let mut subscription = eth.subscribe_new_pending_transactions().await.unwrap();
+        assert_eq!(subscription.id(), "0xbeef");
+
+        let hash = subscription.next().await.unwrap().unwrap();
+        assert_eq!(hash.as_bytes(), &[0x33; 32]);
+
+        assert!(subscription.unsubscribe().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_requests_answered_without_id() {
+        let provider = serve().await;
+
+        match provider.execute("eth_chainId", vec![]).await {
+            Err(Error::Rpc { code: -32600, .. }) => {}
+            result => panic!("unexpected {:?}", result),
+        }
+
+        let requests = vec![
+            Request::new("eth_blockNumber".to_owned(), vec![]),
+            Request::new("eth_gasPrice".to_owned(), vec![]),
+        ];
+
+        match provider.send_batch(requests).await {
+            Err(Error::Rpc { code: -32005, .. }) => {}
+            result => panic!("unexpected {:?}", result),
+        }
+
+        let value = provider.execute("eth_blockNumber", vec![]).await.unwrap();
+        assert_eq!(value["result"], "0x10");
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    fmt, fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use w3b_types::Bytes32;
+
+use crate::error::Error;
+
+/// The secret shared with an execution client to authenticate engine API requests.
+#[derive(Clone)]
+pub struct JwtSecret(Bytes32);
+
+impl JwtSecret {
+    #[inline]
+    pub fn new(secret: Bytes32) -> Self {
+        Self(secret)
+    }
+
+    /// Parses a secret written as 32 hex-encoded bytes, with or without a `0x` prefix.
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        let hex = hex.trim();
+
+        let secret = if hex.starts_with("0x") {
+            Bytes32::from_hex(hex)
+        } else {
+            Bytes32::from_hex_unprefixed(hex)
+        };
+
+        secret
+            .map(Self)
+            .map_err(|error| Error::Provider(Box::new(error)))
+    }
+
+    /// Reads a secret file such as the `jwt.hex` shared with the execution client.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_hex(&fs::read_to_string(path)?)
+    }
+
+    /// Signs an HS256 token issued at the given time, which nodes only accept for about a minute.
+    pub fn sign(&self, issued_at: SystemTime) -> String {
+        let issued_at = issued_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let header = encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+        let claims = encode(format!(r#"{{"iat":{}}}"#, issued_at));
+        let message = format!("{}.{}", header, claims);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.0.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+
+        format!("{}.{}", message, encode(mac.finalize().into_bytes()))
+    }
+}
+
+impl fmt::Debug for JwtSecret {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("JwtSecret(..)")
+    }
+}
+
+#[inline]
+fn encode(bytes: impl AsRef<[u8]>) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::JwtSecret;
+
+    #[test]
+    fn sign_hs256_token() {
+        let hex: String = (0..32u8).map(|byte| format!("{:02x}", byte)).collect();
+        let secret = JwtSecret::from_hex(&format!("0x{}\n", hex)).unwrap();
+
+        assert_eq!(
+            secret.sign(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJpYXQiOjE3MDAwMDAwMDB9.\
+             uAxdaUtr1_HpYPtNDLNlQ_sHMiP5eBV-ra8EjL3ScS8",
+        );
+
+        assert!(JwtSecret::from_hex(&hex).is_ok());
+        assert!(JwtSecret::from_hex("0x1234").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use std::{future::Future, pin::Pin};
+
+use futures::{channel::oneshot, future, TryFutureExt};
+
+use crate::{
+    error::Error,
+    json_rpc::Request,
+    provider::{Provider, PubSubProvider},
+};
+
+pub type LayerResponse = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+
+/// Middleware around each request sent through a [`LayeredProvider`], including every request
+/// of a batch.
+pub trait Layer: Clone + Send + Sync + 'static {
+    /// Handles a request, calling `next` to pass it on to the inner provider, unless answering it
+    /// by other means.
+    ///
+    /// `next` borrows non-`'static` state of the caller, such as the batch being assembled, so it
+    /// must be called synchronously within `call`, never from the returned future; only the
+    /// response it returns may be awaited there.
+    fn call<F>(&self, request: Request, next: F) -> LayerResponse
+    where
+        F: FnOnce(Request) -> LayerResponse;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    RpcError,
+    Failure,
+}
+
+impl Outcome {
+    pub fn of(response: &Result<serde_json::Value, Error>) -> Self {
+        match response {
+            Ok(value) if matches!(value.get("error"), Some(error) if !error.is_null()) => {
+                Outcome::RpcError
+            }
+            Ok(_) => Outcome::Success,
+            Err(_) => Outcome::Failure,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::RpcError => "rpc_error",
+            Outcome::Failure => "failure",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LayeredProvider<P: Provider, L: Layer> {
+    provider: P,
+    layer: L,
+}
+
+impl<P: Provider, L: Layer> LayeredProvider<P, L> {
+    #[inline]
+    pub fn new(provider: P, layer: L) -> Self {
+        Self { provider, layer }
+    }
+}
+
+impl<P: Provider, L: Layer> Provider for LayeredProvider<P, L> {
+    type Response = LayerResponse;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        let provider = &self.provider;
+        self.layer
+            .call(request, |request| Box::pin(provider.send(request)))
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        let mut forwarded = Vec::with_capacity(requests.len());
+
+        let responses: Vec<_> = requests
+            .into_iter()
+            .map(|request| {
+                self.layer.call(request, |request| {
+                    let (sender, receiver) = oneshot::channel();
+                    forwarded.push((request, sender));
+                    Box::pin(receiver.err_into())
+                })
+            })
+            .collect();
+
+        let (requests, senders): (Vec<_>, Vec<_>) = forwarded.into_iter().unzip();
+
+        let batch = if requests.is_empty() {
+            None
+        } else {
+            Some(self.provider.send_batch(requests))
+        };
+
+        Box::pin(async move {
+            // Failing the batch drops the senders, failing the responses still awaited by layers.
+            let dispatch = async move {
+                if let Some(batch) = batch {
+                    for (sender, value) in senders.into_iter().zip(batch.await?) {
+                        let _ = sender.send(value);
+                    }
+                }
+
+                Ok::<_, Error>(())
+            };
+
+            let (dispatched, responses) = future::join(dispatch, future::join_all(responses)).await;
+            dispatched?;
+            responses.into_iter().collect()
+        })
+    }
+}
+
+impl<P: PubSubProvider, L: Layer> PubSubProvider for LayeredProvider<P, L> {
+    type Notifications = P::Notifications;
+    type SubscribeResponse = P::SubscribeResponse;
+
+    #[inline]
+    fn subscribe(&self, request: Request) -> Self::SubscribeResponse {
+        self.provider.subscribe(request)
+    }
+
+    #[inline]
+    fn unsubscribe(&self, subscription_id: String) -> Self::Response {
+        Box::pin(self.provider.unsubscribe(subscription_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::{executor::block_on, future};
+    use serde_json::json;
+
+    use super::{Layer, LayerResponse, LayeredProvider, Outcome};
+    use crate::{json_rpc::Request, provider::Provider, providers::MockProvider};
+
+    /// Records the outcome of each request, answering `eth_chainId` without the inner provider.
+    #[derive(Clone, Default)]
+    struct Record(Arc<Mutex<Vec<(String, Outcome)>>>);
+
+    impl Layer for Record {
+        fn call<F>(&self, request: Request, next: F) -> LayerResponse
+        where
+            F: FnOnce(Request) -> LayerResponse,
+        {
+            let method = request.method().to_owned();
+
+            let response = if method == "eth_chainId" {
+                Box::pin(future::ok(
+                    json!({ "jsonrpc": "2.0", "id": 0, "result": "0x1" }),
+                ))
+            } else {
+                next(request)
+            };
+
+            let outcomes = self.0.clone();
+
+            Box::pin(async move {
+                let response = response.await;
+                outcomes
+                    .lock()
+                    .unwrap()
+                    .push((method, Outcome::of(&response)));
+                response
+            })
+        }
+    }
+
+    #[test]
+    fn layer_each_request_of_a_batch() {
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber").returns("0x2a");
+        mock.expect("eth_getBalance")
+            .rpc_error(-32602, "invalid params", None);
+
+        let record = Record::default();
+        let provider = LayeredProvider::new(mock.clone(), record.clone());
+
+        let responses = block_on(provider.send_batch(vec![
+            Request::new("eth_blockNumber".to_owned(), vec![]),
+            Request::new("eth_chainId".to_owned(), vec![]),
+            Request::new("eth_getBalance".to_owned(), vec![]),
+        ]))
+        .unwrap();
+
+        assert_eq!(responses[0]["result"], "0x2a");
+        assert_eq!(responses[1]["result"], "0x1");
+        assert_eq!(responses[2]["error"]["code"], -32602);
+        assert_eq!(mock.remaining(), 0);
+
+        let mut outcomes = record.0.lock().unwrap().clone();
+        outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            outcomes,
+            vec![
+                ("eth_blockNumber".to_owned(), Outcome::Success),
+                ("eth_chainId".to_owned(), Outcome::Success),
+                ("eth_getBalance".to_owned(), Outcome::RpcError),
+            ],
+        );
+    }
+
+    #[test]
+    fn fail_every_request_of_a_failed_batch() {
+        let mock = MockProvider::new();
+        let record = Record::default();
+        let provider = LayeredProvider::new(mock, record.clone());
+
+        let response = block_on(provider.send_batch(vec![
+            Request::new("eth_blockNumber".to_owned(), vec![]),
+            Request::new("eth_gasPrice".to_owned(), vec![]),
+        ]));
+
+        assert!(response.is_err());
+
+        let outcomes = record.0.lock().unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes
+            .iter()
+            .all(|(_, outcome)| *outcome == Outcome::Failure));
+    }
+}
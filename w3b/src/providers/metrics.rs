@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::layered::{Layer, LayerResponse, Outcome};
+use crate::json_rpc::Request;
+
+/// Latency counts over fixed buckets, each bucket counting durations up to its bound and after
+/// the previous one.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    bounds: Arc<[Duration]>,
+    counts: Vec<u64>,
+    sum: Duration,
+}
+
+impl Histogram {
+    /// Creates a histogram with the given bounds, sorted and without duplicates.
+    pub fn new(bounds: impl Into<Arc<[Duration]>>) -> Self {
+        let mut bounds = bounds.into();
+
+        if !bounds.windows(2).all(|pair| pair[0] < pair[1]) {
+            let mut sorted = bounds.to_vec();
+            sorted.sort();
+            sorted.dedup();
+            bounds = sorted.into();
+        }
+
+        let counts = vec![0; bounds.len() + 1];
+
+        Self {
+            bounds,
+            counts,
+            sum: Duration::default(),
+        }
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        let bucket = self.bounds.partition_point(|bound| *bound < duration);
+        self.counts[bucket] += 1;
+        self.sum += duration;
+    }
+
+    #[inline]
+    pub fn bounds(&self) -> &[Duration] {
+        &self.bounds
+    }
+
+    /// Returns the count of each bucket, followed by the count of durations above every bound.
+    #[inline]
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    #[inline]
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MethodMetrics {
+    pub requests: u64,
+    /// Requests that failed or were answered with a JSON-RPC error.
+    pub errors: u64,
+    pub latency: Histogram,
+}
+
+/// Counts requests, errors and latencies per JSON-RPC method.
+#[derive(Clone)]
+pub struct MetricsLayer {
+    bounds: Arc<[Duration]>,
+    methods: Arc<Mutex<HashMap<String, MethodMetrics>>>,
+}
+
+impl MetricsLayer {
+    /// Creates a layer with latency buckets from 5ms to 10s.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_buckets(
+            [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000]
+                .iter()
+                .map(|millis| Duration::from_millis(*millis))
+                .collect(),
+        )
+    }
+
+    /// Creates a layer with latency buckets up to the given bounds, in any order.
+    #[inline]
+    pub fn with_buckets(mut bounds: Vec<Duration>) -> Self {
+        bounds.sort();
+        bounds.dedup();
+
+        Self {
+            bounds: bounds.into(),
+            methods: Default::default(),
+        }
+    }
+
+    pub fn method(&self, method: &str) -> Option<MethodMetrics> {
+        self.methods.lock().unwrap().get(method).cloned()
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, MethodMetrics> {
+        self.methods.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: String, outcome: Outcome, duration: Duration) {
+        let mut methods = self.methods.lock().unwrap();
+
+        let metrics = methods.entry(method).or_insert_with(|| MethodMetrics {
+            requests: 0,
+            errors: 0,
+            latency: Histogram::new(self.bounds.clone()),
+        });
+
+        metrics.requests += 1;
+        metrics.errors += (outcome != Outcome::Success) as u64;
+        metrics.latency.record(duration);
+    }
+}
+
+impl Default for MetricsLayer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Layer for MetricsLayer {
+    fn call<F>(&self, request: Request, next: F) -> LayerResponse
+    where
+        F: FnOnce(Request) -> LayerResponse,
+    {
+        let method = request.method().to_owned();
+        let response = next(request);
+        let metrics = self.clone();
+
+        Box::pin(async move {
+            let started = Instant::now();
+            let response = response.await;
+            metrics.record(method, Outcome::of(&response), started.elapsed());
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::executor::block_on;
+
+    use super::{Histogram, MetricsLayer};
+    use crate::{
+        api::eth::Eth,
+        namespace::Namespace,
+        providers::{LayeredProvider, MockProvider, TraceLayer},
+    };
+
+    #[test]
+    fn bucket_latencies() {
+        let mut histogram =
+            Histogram::new(vec![Duration::from_millis(10), Duration::from_millis(100)]);
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_millis(10));
+        histogram.record(Duration::from_millis(50));
+        histogram.record(Duration::from_secs(1));
+
+        assert_eq!(histogram.counts(), &[2, 1, 1]);
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum(), Duration::from_millis(1061));
+    }
+
+    #[test]
+    fn sort_bounds() {
+        let mut histogram = Histogram::new(vec![
+            Duration::from_millis(100),
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+        ]);
+        histogram.record(Duration::from_millis(1));
+        histogram.record(Duration::from_millis(50));
+
+        assert_eq!(
+            histogram.bounds(),
+            &[Duration::from_millis(10), Duration::from_millis(100)]
+        );
+        assert_eq!(histogram.counts(), &[1, 1, 0]);
+    }
+
+    #[test]
+    fn count_requests_and_errors_per_method() {
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber").returns("0x1");
+        mock.expect("eth_blockNumber")
+            .rpc_error(-32000, "header not found", None);
+        mock.expect("eth_blockNumber").fails("connection reset");
+
+        let metrics = MetricsLayer::new();
+        let provider = LayeredProvider::new(mock, metrics.clone());
+        let eth = Eth::new(LayeredProvider::new(provider, TraceLayer::new()));
+
+        assert_eq!(block_on(eth.block_number()).unwrap(), 1);
+        assert!(block_on(eth.block_number()).is_err());
+        assert!(block_on(eth.block_number()).is_err());
+
+        let block_number = metrics.method("eth_blockNumber").unwrap();
+        assert_eq!(block_number.requests, 3);
+        assert_eq!(block_number.errors, 2);
+        assert_eq!(block_number.latency.count(), 3);
+        assert!(metrics.method("eth_getLogs").is_none());
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::{self, Ready};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+enum Outcome {
+    Result(serde_json::Value),
+    RpcError {
+        code: i64,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+    Failure(String),
+}
+
+struct Entry {
+    method: String,
+    params: Option<Vec<serde_json::Value>>,
+    outcome: Outcome,
+}
+
+impl Entry {
+    // `Option::is_none_or` needs Rust 1.82.
+    #[allow(clippy::unnecessary_map_or)]
+    fn matches(&self, request: &Request) -> bool {
+        self.method == request.method()
+            && self
+                .params
+                .as_ref()
+                .map_or(true, |params| params.as_slice() == request.params())
+    }
+}
+
+/// Answers requests from queued expectations instead of a node.
+///
+/// Each request consumes the first queued expectation matching its method and params, so that
+/// expectations for concurrent requests may be queued in any order.
+#[derive(Clone, Default)]
+pub struct MockProvider {
+    entries: Arc<Mutex<VecDeque<Entry>>>,
+}
+
+impl MockProvider {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts an expectation for a request, matching any params unless given some.
+    #[inline]
+    pub fn expect(&self, method: &str) -> Expectation<'_> {
+        Expectation {
+            entries: &self.entries,
+            method: method.to_owned(),
+            params: None,
+        }
+    }
+
+    /// Returns the number of expectations not consumed yet.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    fn answer(&self, request: &Request) -> Result<serde_json::Value, Error> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let entry = entries
+            .iter()
+            .position(|entry| entry.matches(request))
+            .and_then(|index| entries.remove(index));
+
+        let entry = match entry {
+            Some(entry) => entry,
+
+            None => {
+                return Err(Error::Provider(
+                    format!(
+                        "unexpected request {} with params {:?}",
+                        request.method(),
+                        request.params(),
+                    )
+                    .into(),
+                ))
+            }
+        };
+
+        let id = request.id();
+
+        match entry.outcome {
+            Outcome::Result(result) => Ok(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+
+            Outcome::RpcError {
+                code,
+                message,
+                data,
+            } => Ok(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message, "data": data },
+            })),
+
+            Outcome::Failure(message) => Err(Error::Provider(message.into())),
+        }
+    }
+}
+
+pub struct Expectation<'a> {
+    entries: &'a Mutex<VecDeque<Entry>>,
+    method: String,
+    params: Option<Vec<serde_json::Value>>,
+}
+
+impl Expectation<'_> {
+    #[inline]
+    pub fn params(mut self, params: Vec<serde_json::Value>) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Answers the request with the given result.
+    pub fn returns(self, result: impl Serialize) {
+        let result = serde_json::to_value(result).unwrap();
+        self.push(Outcome::Result(result));
+    }
+
+    /// Answers the request with a JSON-RPC error object.
+    pub fn rpc_error(self, code: i64, message: &str, data: Option<serde_json::Value>) {
+        let message = message.to_owned();
+
+        self.push(Outcome::RpcError {
+            code,
+            message,
+            data,
+        });
+    }
+
+    /// Fails the request as if the transport did.
+    pub fn fails(self, message: &str) {
+        self.push(Outcome::Failure(message.to_owned()));
+    }
+
+    fn push(self, outcome: Outcome) {
+        self.entries.lock().unwrap().push_back(Entry {
+            method: self.method,
+            params: self.params,
+            outcome,
+        });
+    }
+}
+
+impl Provider for MockProvider {
+    type Response = Ready<Result<serde_json::Value, Error>>;
+    type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+    #[inline]
+    fn send(&self, request: Request) -> Self::Response {
+        future::ready(self.answer(&request))
+    }
+
+    #[inline]
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        future::ready(
+            requests
+                .iter()
+                .map(|request| self.answer(request))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde_json::json;
+    use w3b_types::{Address, BlockNumber};
+
+    use super::MockProvider;
+    use crate::{api::eth::Eth, error::Error, namespace::Namespace};
+
+    #[test]
+    fn match_method_and_params() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_getBalance")
+            .params(vec![json!(format!("0x{}", "0".repeat(40))), json!("0x1")])
+            .returns("0x1");
+
+        mock.expect("eth_getBalance")
+            .params(vec![json!(format!("0x{}", "0".repeat(40))), json!("0x2")])
+            .returns("0x2");
+
+        let address = Address::new([0; 20]);
+        let second = block_on(eth.balance(address.clone(), BlockNumber::Number(2))).unwrap();
+        let first = block_on(eth.balance(address, BlockNumber::Number(1))).unwrap();
+
+        assert_eq!(first, 1_u64.into());
+        assert_eq!(second, 2_u64.into());
+        assert_eq!(mock.remaining(), 0);
+    }
+
+    #[test]
+    fn return_errors() {
+        let mock = MockProvider::new();
+        let eth = Eth::new(mock.clone());
+
+        mock.expect("eth_blockNumber")
+            .rpc_error(-32000, "header not found", None);
+
+        mock.expect("eth_blockNumber").fails("connection reset");
+
+        match block_on(eth.block_number()).unwrap_err() {
+            Error::Rpc { code, .. } => assert_eq!(code, -32000),
+            error => panic!("unexpected error {:?}", error),
+        }
+
+        assert_eq!(
+            block_on(eth.block_number()).unwrap_err().to_string(),
+            "\"connection reset\"",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected request eth_blockNumber with params []")]
+    fn reject_unexpected_requests() {
+        let eth = Eth::new(MockProvider::new());
+        block_on(eth.block_number()).unwrap();
+    }
+}
@@ -1,3 +1,32 @@
+mod caching;
+mod dispatch;
+mod fallback;
 mod http;
+#[cfg(unix)]
+mod ipc;
+mod jwt;
+mod layered;
+mod metrics;
+mod mock;
+mod quorum;
+mod rate_limit;
+mod recording;
+mod retry;
+mod trace;
+mod ws;
 
+pub use caching::*;
+pub use fallback::*;
 pub use http::*;
+#[cfg(unix)]
+pub use ipc::*;
+pub use jwt::*;
+pub use layered::*;
+pub use metrics::*;
+pub use mock::*;
+pub use quorum::*;
+pub use rate_limit::*;
+pub use recording::*;
+pub use retry::*;
+pub use trace::*;
+pub use ws::*;
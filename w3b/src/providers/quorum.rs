@@ -0,0 +1,225 @@
+use std::{error::Error as StdError, fmt, future::Future, pin::Pin, sync::Arc};
+
+use futures::{stream::FuturesUnordered, StreamExt};
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quorum {
+    /// More than half of the total weight.
+    Majority,
+    /// The total weight.
+    All,
+    /// At least the given weight.
+    Weight(u64),
+}
+
+impl Quorum {
+    #[inline]
+    fn weight(self, total: u64) -> u64 {
+        match self {
+            Quorum::Majority => total / 2 + 1,
+            Quorum::All => total,
+            Quorum::Weight(weight) => weight,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub struct QuorumError {
+    /// The highest weight that agreed on a single response.
+    pub weight: u64,
+    pub required: u64,
+}
+
+impl fmt::Debug for QuorumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "quorum not reached at weight {}, expected at least {}",
+            self.weight, self.required
+        )
+    }
+}
+
+impl fmt::Display for QuorumError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl StdError for QuorumError {}
+
+/// Sends every request to all of its weighted providers, and resolves to a response once
+/// providers weighing at least the quorum have answered with the same result or error.
+#[derive(Clone)]
+pub struct QuorumProvider<P: Provider> {
+    providers: Arc<Vec<(P, u64)>>,
+    quorum: Quorum,
+}
+
+impl<P: Provider> QuorumProvider<P> {
+    #[inline]
+    pub fn new(providers: Vec<P>, quorum: Quorum) -> Self {
+        let providers = providers
+            .into_iter()
+            .map(|provider| (provider, 1))
+            .collect();
+        Self::with_weights(providers, quorum)
+    }
+
+    #[inline]
+    pub fn with_weights(providers: Vec<(P, u64)>, quorum: Quorum) -> Self {
+        Self {
+            providers: Arc::new(providers),
+            quorum,
+        }
+    }
+
+    async fn agree<F, R, U, K>(self, call: F, key: K) -> Result<U, Error>
+    where
+        F: Fn(&P) -> R,
+        R: Future<Output = Result<U, Error>>,
+        K: Fn(&U) -> serde_json::Value,
+    {
+        let total = self.providers.iter().map(|(_, weight)| weight).sum();
+        let required = self.quorum.weight(total);
+
+        let mut responses: FuturesUnordered<_> = self
+            .providers
+            .iter()
+            .map(|(provider, weight)| {
+                let response = call(provider);
+                async move { (response.await, *weight) }
+            })
+            .collect();
+
+        let mut tallies: Vec<(serde_json::Value, u64)> = Vec::new();
+        let mut last_error = None;
+
+        while let Some((response, weight)) = responses.next().await {
+            let response = match response {
+                Ok(response) => response,
+
+                Err(error) => {
+                    last_error = Some(error);
+                    continue;
+                }
+            };
+
+            let response_key = key(&response);
+
+            let tally = match tallies.iter_mut().find(|(key, _)| *key == response_key) {
+                Some((_, tally)) => tally,
+
+                None => {
+                    tallies.push((response_key, 0));
+                    &mut tallies.last_mut().unwrap().1
+                }
+            };
+
+            *tally += weight;
+
+            if *tally >= required {
+                return Ok(response);
+            }
+        }
+
+        let weight = tallies.iter().map(|(_, tally)| *tally).max();
+
+        match (weight, last_error) {
+            // Surface the underlying error when no provider answered at all.
+            (None, Some(error)) => Err(error),
+            (weight, _) => Err(Error::Provider(Box::new(QuorumError {
+                weight: weight.unwrap_or_default(),
+                required,
+            }))),
+        }
+    }
+}
+
+/// Strips a response down to the fields providers have to agree on, leaving out the ids that
+/// each provider assigns on its own.
+fn response_key(response: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!([response["result"], response["error"]])
+}
+
+impl<P: Provider> Provider for QuorumProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        Box::pin(
+            self.clone()
+                .agree(move |provider| provider.send(request.clone()), response_key),
+        )
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        Box::pin(self.clone().agree(
+            move |provider| provider.send_batch(requests.clone()),
+            |responses: &Vec<_>| responses.iter().map(response_key).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{
+        executor::block_on,
+        future::{self, Ready},
+    };
+    use serde_json::json;
+
+    use super::{Quorum, QuorumProvider};
+    use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+    #[derive(Clone)]
+    struct Node(Option<&'static str>);
+
+    impl Provider for Node {
+        type Response = Ready<Result<serde_json::Value, Error>>;
+        type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+        fn send(&self, _: Request) -> Self::Response {
+            future::ready(match self.0 {
+                Some(result) => Ok(json!({ "jsonrpc": "2.0", "id": 1, "result": result })),
+                None => Err(Error::Provider("connection refused".into())),
+            })
+        }
+
+        fn send_batch(&self, _: Vec<Request>) -> Self::BatchResponse {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn reach_majority() {
+        let nodes = vec![Node(Some("0x1")), Node(Some("0x2")), Node(Some("0x2"))];
+        let provider = QuorumProvider::new(nodes, Quorum::Majority);
+        let value = block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+        assert_eq!(value["result"], "0x2");
+    }
+
+    #[test]
+    fn reach_weight() {
+        let nodes = vec![
+            (Node(Some("0x1")), 3),
+            (Node(Some("0x2")), 1),
+            (Node(None), 1),
+        ];
+        let provider = QuorumProvider::with_weights(nodes, Quorum::Weight(3));
+        let value = block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+        assert_eq!(value["result"], "0x1");
+    }
+
+    #[test]
+    #[should_panic(expected = "quorum not reached at weight 1, expected at least 2")]
+    fn miss_majority() {
+        let nodes = vec![Node(Some("0x1")), Node(Some("0x2")), Node(None)];
+        let provider = QuorumProvider::new(nodes, Quorum::Majority);
+        block_on(provider.execute("eth_blockNumber", vec![])).unwrap();
+    }
+}
@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::Semaphore, time::delay_for};
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+/// Longest wait before a request, in seconds, so that tiny rates never overflow a [`Duration`].
+const MAX_WAIT: f64 = 24.0 * 60.0 * 60.0;
+
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    /// Units replenished per second, where a request costs the weight of its method.
+    pub per_second: f64,
+    /// Maximum units that can be spent at once after being idle.
+    pub burst: f64,
+    /// Weights of methods heavier than a single unit, such as `eth_getLogs`.
+    pub weights: HashMap<String, u32>,
+    /// Maximum number of requests in flight, if any.
+    pub max_in_flight: Option<usize>,
+}
+
+impl RateLimit {
+    /// Fails unless `per_second` is positive and finite.
+    pub fn new(per_second: f64) -> Result<Self, Error> {
+        let limit = Self {
+            per_second,
+            burst: per_second,
+            weights: HashMap::new(),
+            max_in_flight: None,
+        };
+
+        limit.validate()?;
+        Ok(limit)
+    }
+
+    #[inline]
+    pub fn weight(&self, method: &str) -> u32 {
+        self.weights.get(method).copied().unwrap_or(1)
+    }
+
+    /// Rejects the rates and bursts waits cannot be computed from, such as zero, negative,
+    /// infinite or NaN ones.
+    fn validate(&self) -> Result<(), Error> {
+        if !self.per_second.is_finite() || self.per_second <= 0.0 {
+            return Err(Error::Provider(
+                format!("invalid rate limit of {} per second", self.per_second).into(),
+            ));
+        }
+
+        if !self.burst.is_finite() || self.burst < 0.0 {
+            return Err(Error::Provider(
+                format!("invalid rate limit burst of {}", self.burst).into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A token bucket that lets its balance go negative, so that callers are served in the order
+/// they made their reservations.
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Reserves the given cost, returning how long to wait before spending it.
+    fn reserve(&mut self, limit: &RateLimit, cost: f64, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.per_second).min(limit.burst);
+        self.updated = now;
+        self.tokens -= cost;
+
+        if self.tokens >= 0.0 {
+            Duration::default()
+        } else {
+            Duration::from_secs_f64((-self.tokens / limit.per_second).min(MAX_WAIT))
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitedProvider<P: Provider> {
+    provider: P,
+    limit: Arc<RateLimit>,
+    bucket: Arc<Mutex<Bucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+impl<P: Provider> RateLimitedProvider<P> {
+    #[inline]
+    pub fn new(provider: P, per_second: f64) -> Result<Self, Error> {
+        Self::with_limit(provider, RateLimit::new(per_second)?)
+    }
+
+    /// Fails if the limit was set to a rate that is not positive.
+    pub fn with_limit(provider: P, limit: RateLimit) -> Result<Self, Error> {
+        limit.validate()?;
+
+        let bucket = Bucket {
+            tokens: limit.burst,
+            updated: Instant::now(),
+        };
+
+        let in_flight = limit
+            .max_in_flight
+            .map(|permits| Arc::new(Semaphore::new(permits)));
+
+        Ok(Self {
+            provider,
+            limit: Arc::new(limit),
+            bucket: Arc::new(Mutex::new(bucket)),
+            in_flight,
+        })
+    }
+
+    async fn limit<F, R, U>(self, cost: u32, call: F) -> Result<U, Error>
+    where
+        F: FnOnce(&P) -> R,
+        R: Future<Output = Result<U, Error>>,
+    {
+        let _permit = match self.in_flight.clone() {
+            Some(in_flight) => Some(in_flight.acquire_owned().await),
+            None => None,
+        };
+
+        let wait = self
+            .bucket
+            .lock()
+            .unwrap()
+            .reserve(&self.limit, cost as f64, Instant::now());
+
+        if wait > Duration::default() {
+            delay_for(wait).await;
+        }
+
+        call(&self.provider).await
+    }
+}
+
+impl<P: Provider> Provider for RateLimitedProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        let cost = self.limit.weight(request.method());
+        Box::pin(self.clone().limit(cost, |provider| provider.send(request)))
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        let cost = requests
+            .iter()
+            .map(|request| self.limit.weight(request.method()))
+            .sum();
+
+        Box::pin(
+            self.clone()
+                .limit(cost, |provider| provider.send_batch(requests)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+
+    use futures::future;
+    use serde_json::json;
+    use tokio::time::delay_for;
+
+    use super::{Bucket, RateLimit, RateLimitedProvider, MAX_WAIT};
+    use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+    #[test]
+    fn reserve() {
+        let mut limit = RateLimit::new(10.0).unwrap();
+        limit.burst = 2.0;
+        limit.weights.insert("eth_getLogs".to_owned(), 5);
+
+        let now = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 2.0,
+            updated: now,
+        };
+
+        let cost = limit.weight("eth_blockNumber") as f64;
+        assert_eq!(bucket.reserve(&limit, cost, now), Duration::default());
+        assert_eq!(bucket.reserve(&limit, cost, now), Duration::default());
+        assert_eq!(
+            bucket.reserve(&limit, cost, now),
+            Duration::from_millis(100)
+        );
+
+        let cost = limit.weight("eth_getLogs") as f64;
+        let later = now + Duration::from_millis(100);
+        assert_eq!(
+            bucket.reserve(&limit, cost, later),
+            Duration::from_millis(500)
+        );
+
+        // Idle time never accumulates more than the burst.
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(&limit, 2.0, much_later), Duration::default());
+    }
+
+    #[test]
+    fn reject_invalid_rates() {
+        assert!(RateLimit::new(0.0).is_err());
+        assert!(RateLimit::new(-1.0).is_err());
+        assert!(RateLimit::new(f64::NAN).is_err());
+        assert!(RateLimitedProvider::new(Slow::default(), 0.0).is_err());
+
+        assert!(RateLimit::new(f64::INFINITY).is_err());
+
+        let invalid_bursts = [-1.0, f64::NEG_INFINITY, f64::INFINITY, f64::NAN];
+        for burst in invalid_bursts.iter().copied() {
+            let mut limit = RateLimit::new(1.0).unwrap();
+            limit.burst = burst;
+            assert!(RateLimitedProvider::with_limit(Slow::default(), limit).is_err());
+        }
+
+        let mut limit = RateLimit::new(1.0).unwrap();
+        limit.per_second = f64::NAN;
+        assert!(RateLimitedProvider::with_limit(Slow::default(), limit).is_err());
+
+        // Tiny rates wait as long as allowed instead of overflowing.
+        let limit = RateLimit::new(1e-300).unwrap();
+        let now = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 0.0,
+            updated: now,
+        };
+        assert_eq!(
+            bucket.reserve(&limit, 1.0, now),
+            Duration::from_secs_f64(MAX_WAIT)
+        );
+    }
+
+    /// Answers after a short delay, tracking the highest number of concurrent requests.
+    #[derive(Clone, Default)]
+    struct Slow {
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl Provider for Slow {
+        type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+        type BatchResponse = future::Ready<Result<Vec<serde_json::Value>, Error>>;
+
+        fn send(&self, _: Request) -> Self::Response {
+            let slow = self.clone();
+
+            Box::pin(async move {
+                let in_flight = slow.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                slow.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+                delay_for(Duration::from_millis(10)).await;
+                slow.in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(json!({ "jsonrpc": "2.0", "id": 1, "result": "0x1" }))
+            })
+        }
+
+        fn send_batch(&self, _: Vec<Request>) -> Self::BatchResponse {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn cap_in_flight_requests() {
+        let slow = Slow::default();
+        let mut limit = RateLimit::new(1000.0).unwrap();
+        limit.max_in_flight = Some(2);
+
+        let provider = RateLimitedProvider::with_limit(slow.clone(), limit).unwrap();
+        let requests = (0..6).map(|_| provider.execute("eth_blockNumber", vec![]));
+
+        for response in future::join_all(requests).await {
+            response.unwrap();
+        }
+
+        assert_eq!(slow.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn limit_requests_per_second() {
+        let mut limit = RateLimit::new(100.0).unwrap();
+        limit.burst = 1.0;
+
+        let provider = RateLimitedProvider::with_limit(Slow::default(), limit).unwrap();
+        let started = Instant::now();
+        let requests = (0..5).map(|_| provider.execute("eth_blockNumber", vec![]));
+        future::join_all(requests).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
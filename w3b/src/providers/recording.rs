@@ -0,0 +1,189 @@
+use std::{
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::{self, Ready};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+/// A request and the response it got, as stored in fixture files.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub params: Vec<serde_json::Value>,
+    pub response: serde_json::Value,
+}
+
+impl Fixture {
+    #[inline]
+    fn matches(&self, request: &Request) -> bool {
+        self.method == request.method() && self.params.as_slice() == request.params()
+    }
+}
+
+/// Forwards requests to another provider, writing every request and response pair to a fixture
+/// file that a [`ReplayProvider`] can serve back.
+///
+/// The file is rewritten after each response, so it stays usable if the recording stops abruptly.
+#[derive(Clone)]
+pub struct RecordingProvider<P: Provider> {
+    provider: P,
+    path: Arc<PathBuf>,
+    fixtures: Arc<Mutex<Vec<Fixture>>>,
+}
+
+impl<P: Provider> RecordingProvider<P> {
+    #[inline]
+    pub fn new(provider: P, path: impl AsRef<Path>) -> Self {
+        Self {
+            provider,
+            path: Arc::new(path.as_ref().to_owned()),
+            fixtures: Default::default(),
+        }
+    }
+
+    fn record(&self, request: &Request, response: &serde_json::Value) -> Result<(), Error> {
+        let mut fixtures = self.fixtures.lock().unwrap();
+
+        fixtures.push(Fixture {
+            method: request.method().to_owned(),
+            params: request.params().to_owned(),
+            response: response.clone(),
+        });
+
+        fs::write(&*self.path, serde_json::to_vec_pretty(&*fixtures)?)?;
+        Ok(())
+    }
+}
+
+impl<P: Provider> Provider for RecordingProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        let recording = self.clone();
+        let response = self.provider.send(request.clone());
+
+        Box::pin(async move {
+            let response = response.await?;
+            recording.record(&request, &response)?;
+            Ok(response)
+        })
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        let recording = self.clone();
+        let responses = self.provider.send_batch(requests.clone());
+
+        Box::pin(async move {
+            let responses = responses.await?;
+
+            for (request, response) in requests.iter().zip(&responses) {
+                recording.record(request, response)?;
+            }
+
+            Ok(responses)
+        })
+    }
+}
+
+/// Serves responses from a fixture file written by a [`RecordingProvider`], without any node.
+///
+/// Each request consumes the first unused fixture with the same method and params, so repeated
+/// requests are answered in the order they were recorded in.
+#[derive(Clone)]
+pub struct ReplayProvider {
+    fixtures: Arc<Mutex<Vec<Fixture>>>,
+}
+
+impl ReplayProvider {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let fixtures = serde_json::from_slice(&fs::read(path)?)?;
+        Ok(Self::with_fixtures(fixtures))
+    }
+
+    #[inline]
+    pub fn with_fixtures(fixtures: Vec<Fixture>) -> Self {
+        Self {
+            fixtures: Arc::new(Mutex::new(fixtures)),
+        }
+    }
+
+    fn replay(&self, request: &Request) -> Result<serde_json::Value, Error> {
+        let mut fixtures = self.fixtures.lock().unwrap();
+
+        let index = fixtures
+            .iter()
+            .position(|fixture| fixture.matches(request))
+            .ok_or_else(|| {
+                Error::Provider(
+                    format!(
+                        "no recorded response for {} with params {:?}",
+                        request.method(),
+                        request.params(),
+                    )
+                    .into(),
+                )
+            })?;
+
+        let mut response = fixtures.remove(index).response;
+        response["id"] = request.id().clone();
+        Ok(response)
+    }
+}
+
+impl Provider for ReplayProvider {
+    type Response = Ready<Result<serde_json::Value, Error>>;
+    type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+    #[inline]
+    fn send(&self, request: Request) -> Self::Response {
+        future::ready(self.replay(&request))
+    }
+
+    #[inline]
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        future::ready(
+            requests
+                .iter()
+                .map(|request| self.replay(request))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use futures::executor::block_on;
+
+    use super::{RecordingProvider, ReplayProvider};
+    use crate::{api::eth::Eth, namespace::Namespace, providers::MockProvider};
+
+    #[test]
+    fn record_and_replay() {
+        let path = env::temp_dir().join(format!("w3b-{}-fixtures.json", process::id()));
+
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber").returns("0x10");
+        mock.expect("eth_blockNumber").returns("0x11");
+
+        let eth = Eth::new(RecordingProvider::new(mock, &path));
+        assert_eq!(block_on(eth.block_number()).unwrap(), 0x10);
+        assert_eq!(block_on(eth.block_number()).unwrap(), 0x11);
+
+        let eth = Eth::new(ReplayProvider::open(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(block_on(eth.block_number()).unwrap(), 0x10);
+        assert_eq!(block_on(eth.block_number()).unwrap(), 0x11);
+        assert!(block_on(eth.block_number()).is_err());
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::HashSet, error::Error as StdError, future::Future, io, pin::Pin, slice,
+    time::Duration,
+};
+
+use tokio::time::delay_for;
+use tokio_tungstenite::tungstenite;
+
+use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fraction of each backoff, clamped between 0 and 1, that is randomly shaved off.
+    pub jitter: f64,
+    /// HTTP statuses to retry on.
+    pub statuses: HashSet<u16>,
+    /// JSON-RPC error codes to retry on.
+    pub rpc_codes: HashSet<i64>,
+    /// Whether to retry when the transport itself fails, e.g. on connection resets or timeouts.
+    pub transport_errors: bool,
+    /// Methods never retried, as a node may have applied them before the attempt failed.
+    pub non_idempotent: HashSet<String>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.5,
+            statuses: [429, 502, 503, 504].iter().copied().collect(),
+            // -32005 is the "limit exceeded" code from EIP-1474.
+            rpc_codes: [-32005].iter().copied().collect(),
+            transport_errors: true,
+            non_idempotent: [
+                "eth_sendTransaction",
+                "eth_sendRawTransaction",
+                "personal_sendTransaction",
+            ]
+            .iter()
+            .map(|method| method.to_string())
+            .collect(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns whether requests for the method may be retried.
+    #[inline]
+    pub fn retries(&self, method: &str) -> bool {
+        !self.non_idempotent.contains(method)
+    }
+
+    /// Returns the delay to wait before the next attempt, or `None` if the result is final.
+    pub fn retry_delay(
+        &self,
+        attempt: u32,
+        result: &Result<serde_json::Value, Error>,
+    ) -> Option<Duration> {
+        self.delay(attempt, result.as_ref().map(slice::from_ref))
+    }
+
+    /// Returns the delay to wait before the next attempt of a batch, or `None` if the result is
+    /// final, retrying the whole batch if any of its responses is retryable.
+    pub fn batch_retry_delay(
+        &self,
+        attempt: u32,
+        result: &Result<Vec<serde_json::Value>, Error>,
+    ) -> Option<Duration> {
+        self.delay(attempt, result.as_ref().map(Vec::as_slice))
+    }
+
+    /// Returns the jittered exponential backoff after the given attempt, starting at 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let backoff = backoff.min(self.max_backoff.as_secs_f64()).max(0.0);
+        let jitter = match self.jitter {
+            jitter if jitter.is_nan() => 0.0,
+            jitter => jitter.clamp(0.0, 1.0),
+        };
+        let jitter = jitter * rand::random::<f64>();
+        Duration::from_secs_f64(backoff * (1.0 - jitter))
+    }
+
+    fn delay(
+        &self,
+        attempt: u32,
+        result: Result<&[serde_json::Value], &Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retry_after = match result {
+            Ok(responses) if self.is_retryable_response(responses) => None,
+
+            Err(Error::Status {
+                status,
+                retry_after,
+            }) if self.statuses.contains(status) => *retry_after,
+
+            Err(Error::Rpc { code, .. }) if self.rpc_codes.contains(code) => None,
+            Err(Error::Provider(error))
+                if self.transport_errors && is_transport_error(&**error) =>
+            {
+                None
+            }
+            _ => return None,
+        };
+
+        Some(retry_after.unwrap_or_else(|| self.backoff(attempt)))
+    }
+
+    fn is_retryable_response(&self, responses: &[serde_json::Value]) -> bool {
+        responses
+            .iter()
+            .filter_map(|response| response["error"]["code"].as_i64())
+            .any(|code| self.rpc_codes.contains(&code))
+    }
+}
+
+/// Returns whether the error comes from the transport, e.g. a connection reset or a timeout,
+/// rather than from a request or a configuration that can never succeed.
+fn is_transport_error(error: &(dyn StdError + Send + Sync + 'static)) -> bool {
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        return error.is_timeout() || error.is_connect() || error.is_request() || error.is_body();
+    }
+
+    error.is::<io::Error>()
+        || matches!(
+            error.downcast_ref::<tungstenite::Error>(),
+            Some(tungstenite::Error::Io(_))
+        )
+}
+
+#[derive(Clone)]
+pub struct RetryProvider<P: Provider> {
+    provider: P,
+    policy: RetryPolicy,
+}
+
+impl<P: Provider> RetryProvider<P> {
+    #[inline]
+    pub fn new(provider: P) -> Self {
+        Self::with_policy(provider, RetryPolicy::default())
+    }
+
+    #[inline]
+    pub fn with_policy(provider: P, policy: RetryPolicy) -> Self {
+        Self { provider, policy }
+    }
+}
+
+impl<P: Provider> Provider for RetryProvider<P> {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    fn send(&self, request: Request) -> Self::Response {
+        let provider = self.provider.clone();
+        let policy = self.policy.clone();
+
+        if !policy.retries(request.method()) {
+            return Box::pin(provider.send(request));
+        }
+
+        Box::pin(async move {
+            let mut attempt = 1;
+
+            loop {
+                let result = provider.send(request.clone()).await;
+
+                match policy.retry_delay(attempt, &result) {
+                    Some(delay) => delay_for(delay).await,
+                    None => return result,
+                }
+
+                attempt += 1;
+            }
+        })
+    }
+
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        let provider = self.provider.clone();
+        let policy = self.policy.clone();
+
+        if !requests
+            .iter()
+            .all(|request| policy.retries(request.method()))
+        {
+            return Box::pin(provider.send_batch(requests));
+        }
+
+        Box::pin(async move {
+            let mut attempt = 1;
+
+            loop {
+                let result = provider.send_batch(requests.clone()).await;
+
+                match policy.batch_retry_delay(attempt, &result) {
+                    Some(delay) => delay_for(delay).await,
+                    None => return result,
+                }
+
+                attempt += 1;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::future::{self, Ready};
+    use serde_json::json;
+
+    use super::{RetryPolicy, RetryProvider};
+    use crate::{error::Error, json_rpc::Request, provider::Provider};
+
+    /// Fails with a bad gateway until the given number of attempts has been made.
+    #[derive(Clone)]
+    struct Flaky {
+        attempts: Arc<AtomicU32>,
+        failures: u32,
+    }
+
+    impl Provider for Flaky {
+        type Response = Ready<Result<serde_json::Value, Error>>;
+        type BatchResponse = Ready<Result<Vec<serde_json::Value>, Error>>;
+
+        fn send(&self, _: Request) -> Self::Response {
+            if self.attempts.fetch_add(1, Ordering::Relaxed) < self.failures {
+                future::ready(Err(Error::Status {
+                    status: 502,
+                    retry_after: None,
+                }))
+            } else {
+                future::ready(Ok(json!({ "jsonrpc": "2.0", "id": 1, "result": "0x1" })))
+            }
+        }
+
+        fn send_batch(&self, _: Vec<Request>) -> Self::BatchResponse {
+            unreachable!()
+        }
+    }
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(3), Duration::from_secs(1));
+        assert_eq!(policy.backoff(100), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn retry_delay() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+
+        let rate_limited = Err(Error::Status {
+            status: 429,
+            retry_after: Some(Duration::from_secs(3)),
+        });
+
+        assert_eq!(
+            policy.retry_delay(1, &rate_limited),
+            Some(Duration::from_secs(3)),
+        );
+
+        assert_eq!(policy.retry_delay(5, &rate_limited), None);
+
+        let limit_exceeded = Ok(json!({ "id": 1, "error": { "code": -32005, "message": "" } }));
+        assert_eq!(
+            policy.retry_delay(2, &limit_exceeded),
+            Some(Duration::from_millis(500)),
+        );
+
+        let reverted = Ok(json!({ "id": 1, "error": { "code": 3, "message": "" } }));
+        assert_eq!(policy.retry_delay(1, &reverted), None);
+
+        let batch = Ok(vec![
+            json!({ "id": 1, "result": "0x1" }),
+            json!({ "id": 2, "error": { "code": -32005, "message": "" } }),
+        ]);
+        assert_eq!(
+            policy.batch_retry_delay(1, &batch),
+            Some(Duration::from_millis(250)),
+        );
+    }
+
+    #[test]
+    fn retry_transport_errors_only() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+
+        let reset = Err(io::Error::from(io::ErrorKind::ConnectionReset).into());
+        assert_eq!(
+            policy.retry_delay(1, &reset),
+            Some(Duration::from_millis(250)),
+        );
+
+        let missing = Err(Error::Provider("missing batch response".into()));
+        assert_eq!(policy.retry_delay(1, &missing), None);
+
+        let canceled = Err(futures::channel::oneshot::Canceled.into());
+        assert_eq!(policy.retry_delay(1, &canceled), None);
+    }
+
+    #[test]
+    fn clamp_jitter() {
+        for jitter in &[-1.0, 2.0, f64::NAN] {
+            let policy = RetryPolicy {
+                jitter: *jitter,
+                ..Default::default()
+            };
+
+            assert!(policy.backoff(1) <= Duration::from_millis(250));
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_until_success() {
+        let flaky = Flaky {
+            attempts: Default::default(),
+            failures: 2,
+        };
+
+        let provider = RetryProvider::with_policy(flaky.clone(), policy(3));
+        let value = provider.execute("eth_blockNumber", vec![]).await.unwrap();
+
+        assert_eq!(value["result"], "0x1");
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn never_retry_non_idempotent_methods() {
+        let flaky = Flaky {
+            attempts: Default::default(),
+            failures: 1,
+        };
+
+        let provider = RetryProvider::with_policy(flaky.clone(), policy(3));
+        let error = provider
+            .execute("eth_sendRawTransaction", vec![json!("0x00")])
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "HTTP status 502");
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_attempts() {
+        let flaky = Flaky {
+            attempts: Default::default(),
+            failures: 5,
+        };
+
+        let provider = RetryProvider::with_policy(flaky.clone(), policy(3));
+        let error = provider
+            .execute("eth_blockNumber", vec![])
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "HTTP status 502");
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 3);
+    }
+}
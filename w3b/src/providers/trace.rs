@@ -0,0 +1,167 @@
+use std::time::Instant;
+
+use tracing::{debug_span, field, Instrument};
+
+use super::layered::{Layer, LayerResponse, Outcome};
+use crate::json_rpc::Request;
+
+/// Wraps each request in a `json_rpc` span recording its method, the id the inner provider gave
+/// it, its duration in milliseconds and its outcome.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TraceLayer;
+
+impl TraceLayer {
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Layer for TraceLayer {
+    fn call<F>(&self, request: Request, next: F) -> LayerResponse
+    where
+        F: FnOnce(Request) -> LayerResponse,
+    {
+        let span = debug_span!(
+            "json_rpc",
+            method = request.method(),
+            id = field::Empty,
+            duration_ms = field::Empty,
+            outcome = field::Empty,
+        );
+
+        let response = span.in_scope(|| next(request));
+
+        Box::pin(async move {
+            let started = Instant::now();
+            let response = response.instrument(span.clone()).await;
+
+            span.record("duration_ms", started.elapsed().as_millis() as u64);
+            span.record("outcome", Outcome::of(&response).as_str());
+
+            if let Ok(value) = &response {
+                span.record("id", field::display(&value["id"]));
+            }
+
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use futures::executor::block_on;
+    use tracing::{
+        field::{Field, Visit},
+        span, subscriber, Event, Metadata, Subscriber,
+    };
+    use w3b_types::Address;
+
+    use super::TraceLayer;
+    use crate::{
+        api::eth::Eth,
+        namespace::Namespace,
+        providers::{LayeredProvider, MockProvider},
+    };
+
+    #[derive(Default)]
+    struct Fields(HashMap<&'static str, String>);
+
+    /// Collects the fields of every span by name, in the order the spans were created.
+    #[derive(Clone, Default)]
+    struct Spans {
+        next_id: Arc<AtomicU64>,
+        spans: Arc<Mutex<Vec<(&'static str, Fields)>>>,
+    }
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name(), format!("{:?}", value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name(), value.to_owned());
+        }
+    }
+
+    impl Subscriber for Spans {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes) -> span::Id {
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+            self.spans
+                .lock()
+                .unwrap()
+                .push((span.metadata().name(), fields));
+
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+
+        fn record(&self, id: &span::Id, values: &span::Record) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut spans[id.into_u64() as usize - 1].1);
+        }
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn record_span_fields() {
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber").returns("0x1");
+        mock.expect("eth_gasPrice")
+            .rpc_error(-32601, "method not found", None);
+        mock.expect("eth_getCode").fails("connection reset");
+
+        let eth = Eth::new(LayeredProvider::new(mock, TraceLayer::new()));
+        let spans = Spans::default();
+
+        subscriber::with_default(spans.clone(), || {
+            assert!(block_on(eth.block_number()).is_ok());
+            assert!(block_on(eth.gas_price()).is_err());
+            assert!(block_on(eth.code(Address::new([0x11; 20]), None)).is_err());
+        });
+
+        let spans = spans.spans.lock().unwrap();
+        let recorded: Vec<_> = spans
+            .iter()
+            .map(|(name, Fields(fields))| {
+                (*name, fields["method"].as_str(), fields["outcome"].as_str())
+            })
+            .collect();
+        assert_eq!(
+            recorded,
+            vec![
+                ("json_rpc", "eth_blockNumber", "success"),
+                ("json_rpc", "eth_gasPrice", "rpc_error"),
+                ("json_rpc", "eth_getCode", "failure"),
+            ],
+        );
+
+        let Fields(fields) = &spans[0].1;
+        assert!(fields.contains_key("id"));
+        assert!(fields.contains_key("duration_ms"));
+
+        // Failed requests have no response to take the id from.
+        let Fields(fields) = &spans[2].1;
+        assert!(!fields.contains_key("id"));
+    }
+}
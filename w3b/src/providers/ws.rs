@@ -0,0 +1,279 @@
+use std::{future::Future, pin::Pin};
+
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use tokio_tls::TlsConnector;
+use tokio_tungstenite::{
+    client_async,
+    tungstenite::{
+        client::{uri_mode, IntoClientRequest},
+        stream::Mode,
+        Message,
+    },
+    WebSocketStream,
+};
+
+use super::dispatch::{Command, Dispatcher, Handle, Notifications};
+use crate::{
+    error::Error,
+    json_rpc::Request,
+    provider::{Provider, PubSubProvider},
+};
+
+#[derive(Clone)]
+pub struct WsProvider {
+    handle: Handle,
+}
+
+impl WsProvider {
+    pub async fn connect(uri: impl AsRef<str>) -> Result<Self, Error> {
+        let request = uri.as_ref().into_client_request()?;
+        let mode = uri_mode(request.uri())?;
+        let host = request.uri().host().unwrap_or_default().to_owned();
+
+        let port = request.uri().port_u16().unwrap_or(match mode {
+            Mode::Plain => 80,
+            Mode::Tls => 443,
+        });
+
+        let stream = TcpStream::connect((&*host, port)).await?;
+
+        match mode {
+            Mode::Plain => {
+                let (stream, _) = client_async(request, stream).await?;
+                Ok(Self::with_stream(stream))
+            }
+
+            Mode::Tls => {
+                let connector = TlsConnector::from(native_tls::TlsConnector::new()?);
+                let stream = connector.connect(&host, stream).await?;
+                let (stream, _) = client_async(request, stream).await?;
+                Ok(Self::with_stream(stream))
+            }
+        }
+    }
+
+    pub fn with_stream<S>(stream: WebSocketStream<S>) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (handle, commands) = Handle::new();
+        tokio::spawn(run(stream, commands));
+        Self { handle }
+    }
+}
+
+impl Provider for WsProvider {
+    type Response = Pin<Box<dyn Future<Output = Result<serde_json::Value, Error>> + Send>>;
+    type BatchResponse =
+        Pin<Box<dyn Future<Output = Result<Vec<serde_json::Value>, Error>> + Send>>;
+
+    #[inline]
+    fn send(&self, request: Request) -> Self::Response {
+        Box::pin(self.handle.request(request, None))
+    }
+
+    #[inline]
+    fn send_batch(&self, requests: Vec<Request>) -> Self::BatchResponse {
+        Box::pin(self.handle.batch(requests))
+    }
+}
+
+impl PubSubProvider for WsProvider {
+    type Notifications = Notifications;
+    type SubscribeResponse = Pin<
+        Box<dyn Future<Output = Result<(serde_json::Value, Self::Notifications), Error>> + Send>,
+    >;
+
+    #[inline]
+    fn subscribe(&self, request: Request) -> Self::SubscribeResponse {
+        Box::pin(self.handle.subscribe(request))
+    }
+
+    fn unsubscribe(&self, subscription_id: String) -> Self::Response {
+        self.handle.forget(subscription_id.clone());
+        self.execute("eth_unsubscribe", vec![subscription_id.into()])
+    }
+}
+
+async fn run<S>(stream: WebSocketStream<S>, mut commands: mpsc::UnboundedReceiver<Command>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut sink, mut stream) = stream.split();
+    let mut dispatcher = Dispatcher::default();
+
+    loop {
+        tokio::select! {
+            command = commands.next() => match command {
+                Some(command) => {
+                    if let Some(payload) = dispatcher.register(command) {
+                        if sink.send(Message::Text(payload)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+
+                // Every provider handle has been dropped.
+                None => break,
+            },
+
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let _ = dispatcher.dispatch(text.as_bytes());
+                }
+
+                Some(Ok(Message::Binary(bytes))) => {
+                    let _ = dispatcher.dispatch(&bytes);
+                }
+
+                Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {}
+
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+    use serde_json::json;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+    use w3b_types::Bytes32;
+
+    use super::WsProvider;
+    use crate::{
+        api::eth::Eth, error::Error, json_rpc::Request, namespace::Namespace, provider::Provider,
+    };
+
+    async fn serve() -> String {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = accept_async(stream).await.unwrap();
+            let mut held = Vec::new();
+
+            while let Some(Ok(Message::Text(text))) = stream.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let id = request["id"].clone();
+
+                let replies = match request["method"].as_str().unwrap_or("batch") {
+                    // Nodes rejecting a batch or a request they cannot parse answer a null id.
+                    "batch" => vec![json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": { "code": -32005, "message": "batch limit exceeded" },
+                    })],
+
+                    "eth_chainId" => vec![json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": { "code": -32600, "message": "invalid request" },
+                    })],
+
+                    // Hold the first request back so that responses arrive out of order.
+                    "eth_blockNumber" if held.is_empty() => {
+                        held.push(json!({ "jsonrpc": "2.0", "id": id, "result": "0x1" }));
+                        continue;
+                    }
+
+                    "eth_blockNumber" => {
+                        let mut replies =
+                            vec![json!({ "jsonrpc": "2.0", "id": id, "result": "0x2" })];
+                        replies.append(&mut held);
+                        replies
+                    }
+
+                    "eth_subscribe" => vec![
+                        json!({ "jsonrpc": "2.0", "id": id, "result": "0xcafe" }),
+                        json!({
+                            "jsonrpc": "2.0",
+                            "method": "eth_subscription",
+                            "params": { "subscription": "0xcafe", "result": format!("0x{}", "1".repeat(64)) },
+                        }),
+                        json!({
+                            "jsonrpc": "2.0",
+                            "method": "eth_subscription",
+                            "params": { "subscription": "0xcafe", "result": format!("0x{}", "2".repeat(64)) },
+                        }),
+                    ],
+
+                    "eth_unsubscribe" => {
+                        vec![json!({ "jsonrpc": "2.0", "id": id, "result": true })]
+                    }
+                    _ => unreachable!(),
+                };
+
+                for reply in replies {
+                    stream.send(Message::Text(reply.to_string())).await.unwrap();
+                }
+            }
+        });
+
+        uri
+    }
+
+    #[tokio::test]
+    async fn multiplex_out_of_order_responses() {
+        let provider = WsProvider::connect(serve().await).await.unwrap();
+
+        let (first, second) = futures::join!(
+            provider.execute("eth_blockNumber", vec![]),
+            provider.execute("eth_blockNumber", vec![]),
+        );
+
+        assert_eq!(first.unwrap()["result"], "0x1");
+        assert_eq!(second.unwrap()["result"], "0x2");
+    }
+
+    #[tokio::test]
+    async fn subscribe_new_pending_transactions() {
+        let provider = WsProvider::connect(serve().await).await.unwrap();
+        let eth = Eth::new(provider);
+
+        let mut subscription = eth.subscribe_new_pending_transactions().await.unwrap();
+        assert_eq!(subscription.id(), "0xcafe");
+
+        let hash = subscription.next().await.unwrap().unwrap();
+        assert_eq!(hash, Bytes32::new([0x11; 32]));
+
+        let hash = subscription.next().await.unwrap().unwrap();
+        assert_eq!(hash, Bytes32::new([0x22; 32]));
+
+        assert!(subscription.unsubscribe().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_requests_answered_without_id() {
+        let provider = WsProvider::connect(serve().await).await.unwrap();
+
+        match provider.execute("eth_chainId", vec![]).await {
+            Err(Error::Rpc { code: -32600, .. }) => {}
+            result => panic!("unexpected {:?}", result),
+        }
+
+        let requests = vec![
+            Request::new("eth_blockNumber".to_owned(), vec![]),
+            Request::new("eth_gasPrice".to_owned(), vec![]),
+        ];
+
+        match provider.send_batch(requests).await {
+            Err(Error::Rpc { code: -32005, .. }) => {}
+            result => panic!("unexpected {:?}", result),
+        }
+
+        // Later requests are still answered.
+        let value = provider
+            .execute("eth_unsubscribe", vec![json!("0xcafe")])
+            .await
+            .unwrap();
+        assert_eq!(value["result"], true);
+    }
+}
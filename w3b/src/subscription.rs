@@ -0,0 +1,51 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{ready, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use super::{error::Error, json_rpc::Response, provider::PubSubProvider};
+
+pub struct Subscription<T: PubSubProvider, U> {
+    provider: T,
+    id: String,
+    notifications: T::Notifications,
+    _marker: PhantomData<fn() -> U>,
+}
+
+impl<T: PubSubProvider, U> Subscription<T, U> {
+    #[inline]
+    pub fn new(provider: T, id: String, notifications: T::Notifications) -> Self {
+        Self {
+            provider,
+            id,
+            notifications,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub async fn unsubscribe(self) -> Result<bool, Error> {
+        let value = self.provider.unsubscribe(self.id).await?;
+        let response: Response = serde_json::from_value(value)?;
+        response.into_result()
+    }
+}
+
+impl<T: PubSubProvider, U> Unpin for Subscription<T, U> {}
+
+impl<T: PubSubProvider, U: DeserializeOwned> Stream for Subscription<T, U> {
+    type Item = Result<U, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let value = ready!(self.get_mut().notifications.poll_next_unpin(cx));
+        Poll::Ready(value.map(|value| Ok(serde_json::from_value(value)?)))
+    }
+}
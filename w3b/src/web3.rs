@@ -1,5 +1,6 @@
-use super::{api::*, namespace::Namespace, provider::Provider};
+use super::{api::*, batch::Batch, namespace::Namespace, provider::Provider};
 
+#[derive(Clone)]
 pub struct Web3<T: Provider> {
     provider: T,
 }
@@ -19,4 +20,63 @@ impl<T: Provider> Web3<T> {
     pub fn eth(&self) -> eth::Eth<T> {
         self.namespace()
     }
+
+    pub fn net(&self) -> net::Net<T> {
+        self.namespace()
+    }
+
+    pub fn web3(&self) -> web3_api::Web3Api<T> {
+        self.namespace()
+    }
+
+    pub fn txpool(&self) -> txpool::TxPool<T> {
+        self.namespace()
+    }
+
+    pub fn debug(&self) -> debug::Debug<T> {
+        self.namespace()
+    }
+
+    pub fn trace(&self) -> trace::Trace<T> {
+        self.namespace()
+    }
+
+    pub fn batch(&self) -> Batch<T> {
+        Batch::new(self.provider.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use w3b_types::*;
+
+    use super::Web3;
+    use crate::providers::{HttpProvider, WsProvider};
+
+    fn assert_send<T: Send>(_: T) {}
+
+    fn assert_spawnable<T: Future + Send + 'static>(_: T) {}
+
+    #[test]
+    fn futures_are_send() {
+        // Only type-checked, never run.
+        let _ = |web3: Web3<HttpProvider>, address: Address, filter: Filter| {
+            assert_send(web3.clone());
+            assert_spawnable(web3.eth().block_number());
+            assert_spawnable(web3.eth().balance(address.clone(), None));
+            assert_spawnable(web3.eth().transaction_count(address, None));
+            assert_spawnable(web3.eth().logs(filter));
+
+            let batch = web3.batch();
+            assert_spawnable(batch.eth().block_number());
+            assert_send(batch.execute());
+        };
+
+        let _ = |web3: Web3<WsProvider>| {
+            assert_send(web3.eth().subscribe_new_heads());
+            assert_send(web3.eth().subscribe_new_pending_transactions());
+        };
+    }
 }